@@ -48,6 +48,11 @@ impl TauriCommands {
     pub const UPDATE_MCP_SERVER_CONNECTION: &'static str = "update_mcp_server_connection";
     pub const DELETE_MCP_SERVER_CONNECTION: &'static str = "delete_mcp_server_connection";
     pub const UPDATE_MCP_SERVER_STATUS: &'static str = "update_mcp_server_status";
+    pub const PREVIEW_MCP_SERVER_IMPORT: &'static str = "preview_mcp_server_import";
+    pub const IMPORT_MCP_SERVER_CONNECTIONS: &'static str = "import_mcp_server_connections";
+    pub const EXPORT_MCP_SERVER_CONNECTIONS: &'static str = "export_mcp_server_connections";
+    pub const AUTHORIZE_MCP_SERVER_CONNECTION: &'static str = "authorize_mcp_server_connection";
+    pub const CLEAR_MCP_SERVER_AUTHORIZATION: &'static str = "clear_mcp_server_authorization";
 
     // App Settings commands
     pub const SAVE_APP_SETTING: &'static str = "save_app_setting";
@@ -66,6 +71,11 @@ impl TauriCommands {
     pub const CALL_MCP_TOOL: &'static str = "call_mcp_tool";
     pub const DISCONNECT_MCP_CLIENT: &'static str = "disconnect_mcp_client";
     pub const GET_ACTIVE_TOOLS_FOR_WORKSPACE: &'static str = "get_active_tools_for_workspace";
+    pub const GET_MCP_SERVER_PROCESSES: &'static str = "get_mcp_server_processes";
+    pub const GET_MCP_SERVER_PROCESS_STATUS: &'static str = "get_mcp_server_process_status";
+    pub const GET_MCP_SERVER_LOGS: &'static str = "get_mcp_server_logs";
+    pub const RESTART_MCP_SERVER_PROCESS: &'static str = "restart_mcp_server_process";
+    pub const STOP_MCP_SERVER_PROCESS: &'static str = "stop_mcp_server_process";
 
     // Python commands
     pub const GET_PYTHON_RUNTIMES_STATUS: &'static str = "get_python_runtimes_status";
@@ -215,6 +215,10 @@ pub fn run_migrations(conn: &Connection) -> Result<()> {
         "ALTER TABLE mcp_server_connections ADD COLUMN env_vars TEXT",
         [],
     );
+    let _ = conn.execute(
+        "ALTER TABLE mcp_server_connections ADD COLUMN tool_policies TEXT",
+        [],
+    );
 
     // Create app_settings table
     conn.execute(
@@ -37,6 +37,11 @@ pub enum AppError {
     #[error("[MCP] {0}")]
     Mcp(String),
 
+    /// Transport-level MCP failure (connection refused, dropped stream, process exit)
+    /// that may succeed when retried.
+    #[error("[MCP Transport] {0}")]
+    McpTransport(String),
+
     #[error("[Zip] {0}")]
     Zip(#[from] zip::result::ZipError),
 
@@ -48,6 +53,15 @@ pub enum AppError {
 }
 
 impl AppError {
+    /// Whether retrying the failed operation may succeed.
+    pub fn is_transient(&self) -> bool {
+        match self {
+            Self::McpTransport(_) => true,
+            Self::Http(e) => e.is_timeout() || e.is_connect(),
+            _ => false,
+        }
+    }
+
     /// Report this error to Sentry with optional context
     #[allow(dead_code)]
     pub fn report_to_sentry(&self, context: Option<&str>) {
@@ -168,7 +168,78 @@ impl ArtifactService {
             .await
             .map_err(|e| AppError::Generic(format!("Cannot write artifact file: {e}")))?;
 
-        let size_bytes = content.len() as i64;
+        self.record(
+            ctx,
+            artifact_id,
+            title,
+            filename.trim(),
+            &file_path,
+            Self::mime_type_for_extension(&ext),
+            content.len(),
+        )
+    }
+
+    /// Save binary tool output (images, audio, embedded blobs) as an artifact.
+    ///
+    /// Unlike [`Self::create`], the extension is not restricted since the bytes come from a
+    /// tool result rather than model-authored source text.
+    pub async fn create_binary(
+        &self,
+        ctx: &ToolExecutionContext,
+        title: &str,
+        filename: &str,
+        bytes: &[u8],
+        mime_type: &str,
+    ) -> Result<Artifact, AppError> {
+        let filename = filename.trim();
+        if filename.is_empty()
+            || filename.contains('/')
+            || filename.contains('\\')
+            || filename.contains("..")
+        {
+            return Err(AppError::Validation(format!(
+                "Invalid artifact filename: {filename}"
+            )));
+        }
+        let ext = Path::new(filename)
+            .extension()
+            .and_then(|e| e.to_str())
+            .unwrap_or("bin")
+            .to_lowercase();
+        let chat_dir = Self::ensure_artifact_dir(&ctx.app, &ctx.chat_id)?;
+
+        let artifact_id = uuid::Uuid::new_v4().to_string();
+        let file_path = Self::resolve_disk_path(&chat_dir, filename, &artifact_id, &ext);
+
+        Self::ensure_path_within_chat_dir(&chat_dir, &file_path)?;
+
+        fs::write(&file_path, bytes)
+            .await
+            .map_err(|e| AppError::Generic(format!("Cannot write artifact file: {e}")))?;
+
+        self.record(
+            ctx,
+            artifact_id,
+            title,
+            filename,
+            &file_path,
+            mime_type,
+            bytes.len(),
+        )
+    }
+
+    /// Insert the artifact row for a file already written to disk and notify the frontend.
+    #[allow(clippy::too_many_arguments)]
+    fn record(
+        &self,
+        ctx: &ToolExecutionContext,
+        artifact_id: String,
+        title: &str,
+        filename: &str,
+        file_path: &Path,
+        mime_type: &str,
+        size_bytes: usize,
+    ) -> Result<Artifact, AppError> {
         let now = std::time::SystemTime::now()
             .duration_since(std::time::UNIX_EPOCH)
             .unwrap()
@@ -180,10 +251,10 @@ impl ArtifactService {
             message_id: Some(ctx.message_id.clone()),
             tool_call_id: Some(ctx.tool_call_id.clone()),
             title: title.to_string(),
-            filename: filename.trim().to_string(),
+            filename: filename.to_string(),
             path: file_path.to_string_lossy().to_string(),
-            mime_type: Some(Self::mime_type_for_extension(&ext).to_string()),
-            size_bytes: Some(size_bytes),
+            mime_type: Some(mime_type.to_string()),
+            size_bytes: Some(size_bytes as i64),
             created_at: now,
         };
 
@@ -9,6 +9,7 @@ use crate::features::workspace::settings::WorkspaceSettings;
 use crate::models::llm_types::ToolCall;
 use crate::state::AppState;
 use async_trait::async_trait;
+use std::collections::HashSet;
 use std::sync::Arc;
 use tauri::{AppHandle, Manager};
 
@@ -132,6 +133,7 @@ impl HarnessHooks for TauriHarnessHooks {
         assistant_message_id: &str,
         tool_calls: Vec<ToolCall>,
         workspace_settings: &WorkspaceSettings,
+        sensitive_tools: &HashSet<String>,
     ) -> Result<Vec<ToolCall>, AppError> {
         filter_tool_permissions(
             app,
@@ -139,6 +141,7 @@ impl HarnessHooks for TauriHarnessHooks {
             assistant_message_id,
             tool_calls,
             workspace_settings,
+            sensitive_tools,
         )
         .await
     }
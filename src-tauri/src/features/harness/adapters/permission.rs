@@ -2,7 +2,7 @@ use crate::error::AppError;
 use crate::events::ToolEmitter;
 use crate::features::workspace::settings::WorkspaceSettings;
 use crate::models::llm_types::ToolCall;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use tauri::{AppHandle, Manager};
 
 pub async fn filter_tool_permissions(
@@ -11,6 +11,7 @@ pub async fn filter_tool_permissions(
     assistant_message_id: &str,
     tool_calls: Vec<ToolCall>,
     workspace_settings: &WorkspaceSettings,
+    sensitive_tools: &HashSet<String>,
 ) -> Result<Vec<ToolCall>, AppError> {
     let tool_emitter = ToolEmitter::new(app.clone());
 
@@ -25,6 +26,10 @@ pub async fn filter_tool_permissions(
         if tc.function.name == "ask_user" {
             return false;
         }
+        // Sensitive tools always ask, even when the workspace config says "auto".
+        if sensitive_tools.contains(&tc.function.name) {
+            return true;
+        }
         matches!(
             tool_permission_config
                 .get(&tc.function.name)
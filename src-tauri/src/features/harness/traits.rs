@@ -5,6 +5,7 @@ use crate::features::workspace::settings::WorkspaceSettings;
 use crate::models::llm_types::{LlmChatParams, LLMChatResponse, ToolCall};
 use async_trait::async_trait;
 use serde_json::Value;
+use std::collections::HashSet;
 use std::sync::Arc;
 use tauri::AppHandle;
 use tokio::sync::broadcast;
@@ -139,6 +140,7 @@ pub trait HarnessHooks: Send + Sync {
         assistant_message_id: &str,
         tool_calls: Vec<ToolCall>,
         workspace_settings: &WorkspaceSettings,
+        sensitive_tools: &HashSet<String>,
     ) -> Result<Vec<ToolCall>, AppError>;
 
     async fn on_tool_execution_started(
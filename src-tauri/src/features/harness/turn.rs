@@ -7,7 +7,8 @@ use crate::features::tool::core::{
     parse_tool_arguments, ToolExecutionContext, ToolInteraction, ToolRuntime,
 };
 use crate::models::llm_types::{
-    AssistantContent, ChatMessage, LlmChatParams, LLMChatResponse, UserContent,
+    detect_model_capabilities, AssistantContent, ChatMessage, ContentPart, ImageUrl,
+    LlmChatParams, LLMChatResponse, UserContent,
 };
 use std::sync::Arc;
 use tauri::AppHandle;
@@ -66,6 +67,7 @@ impl ConversationTurnController {
         let stream_options = serde_json::json!({
             "include_usage": true
         });
+        let forward_tool_images = detect_model_capabilities(&model).input.image;
 
         for iteration in 0..=max_iterations {
             let is_last_iteration = iteration == max_iterations;
@@ -182,6 +184,7 @@ impl ConversationTurnController {
                             &assistant_message_id,
                             tool_calls.clone(),
                             &workspace_settings,
+                            &tool_runtime.sensitive_tool_names(),
                         )
                         .await?;
 
@@ -191,6 +194,7 @@ impl ConversationTurnController {
                             &assistant_message_id,
                             &tool_runtime,
                             &allowed_tools,
+                            forward_tool_images,
                             &app,
                             cancellation_rx,
                         )
@@ -261,6 +265,7 @@ impl ConversationTurnController {
         assistant_message_id: &str,
         tool_runtime: &Arc<ToolRuntime>,
         tool_calls: &[crate::models::llm_types::ToolCall],
+        forward_images: bool,
         app: &AppHandle,
         cancellation_rx: &mut broadcast::Receiver<()>,
     ) -> Result<Vec<ChatMessage>, AppError> {
@@ -273,6 +278,9 @@ impl ConversationTurnController {
             .await?;
 
         let mut tool_results: Vec<ChatMessage> = Vec::new();
+        // Tool messages are text-only, so images from tool results are forwarded to
+        // vision-capable models as a follow-up user message.
+        let mut tool_images: Vec<ContentPart> = Vec::new();
         let mut successful_count = 0;
         let mut failed_count = 0;
 
@@ -348,9 +356,39 @@ impl ConversationTurnController {
                 )
                 .await;
 
+            let execution_result = match execution_result {
+                Ok(tool_result) if tool_result.is_error => Err(tool_result.content),
+                Ok(tool_result) => Ok(tool_result),
+                Err(AppError::Cancelled) => return Err(AppError::Cancelled),
+                Err(e) => Err(e.to_string()),
+            };
+
+            let mut tool_metadata = None;
             let (_result, llm_content) = match execution_result {
                 Ok(tool_result) => {
                     successful_count += 1;
+                    if !tool_result.metadata.is_null() {
+                        tool_metadata = Some(tool_result.metadata.to_string());
+                    }
+                    if forward_images {
+                        let images: Vec<ContentPart> = tool_result
+                            .images()
+                            .map(|(mime_type, data)| ContentPart::ImageUrl {
+                                image_url: ImageUrl {
+                                    url: format!("data:{mime_type};base64,{data}"),
+                                },
+                            })
+                            .collect();
+                        if !images.is_empty() {
+                            tool_images.push(ContentPart::Text {
+                                text: format!(
+                                    "Image output from tool `{}` (call {}):",
+                                    tool_call.function.name, tool_call.id
+                                ),
+                            });
+                            tool_images.extend(images);
+                        }
+                    }
                     let llm_text = tool_result.to_llm_content();
                     if tool_result.truncated {
                         tracing::warn!(
@@ -390,12 +428,8 @@ impl ConversationTurnController {
 
                     (result_value, llm_text)
                 }
-                Err(e) => {
-                    if matches!(e, AppError::Cancelled) {
-                        return Err(AppError::Cancelled);
-                    }
+                Err(error_msg) => {
                     failed_count += 1;
-                    let error_msg = e.to_string();
 
                     tracing::error!(
                         tool = %tool_call.function.name,
@@ -455,7 +489,7 @@ impl ConversationTurnController {
                 Some(tool_call_timestamp),
                 None,
                 Some(tool_call.id.clone()),
-                None,
+                tool_metadata,
             )?;
 
             tool_results.push(ChatMessage::Tool {
@@ -464,6 +498,12 @@ impl ConversationTurnController {
             });
         }
 
+        if !tool_images.is_empty() {
+            tool_results.push(ChatMessage::User {
+                content: UserContent::Parts(tool_images),
+            });
+        }
+
         hooks
             .on_tool_execution_completed(
                 chat_id,
@@ -1,6 +1,8 @@
-use super::models::MCPServerConnection;
+use super::models::{
+    MCPImportConflictStrategy, MCPServerConnection, MCPServerImportPreview, MCPServerImportResult,
+};
 use crate::error::AppError;
-use crate::state::AppState;
+use crate::state::{AppState, MCPClientState};
 use tauri::State;
 
 #[tauri::command]
@@ -12,11 +14,21 @@ pub fn create_mcp_server_connection(
     headers: String,
     env_vars: Option<String>,
     runtime_path: Option<String>,
+    tool_policies: Option<String>,
     state: State<'_, AppState>,
 ) -> Result<MCPServerConnection, AppError> {
     state
         .mcp_connection_service
-        .create(id, name, url, r#type, headers, env_vars, runtime_path)
+        .create(
+            id,
+            name,
+            url,
+            r#type,
+            headers,
+            env_vars,
+            runtime_path,
+            tool_policies,
+        )
         .map_err(|e| AppError::Mcp(e.to_string()))
 }
 
@@ -39,11 +51,21 @@ pub fn update_mcp_server_connection(
     headers: Option<String>,
     env_vars: Option<String>,
     runtime_path: Option<String>,
+    tool_policies: Option<String>,
     state: State<'_, AppState>,
 ) -> Result<(), AppError> {
     state
         .mcp_connection_service
-        .update(id, name, url, r#type, headers, env_vars, runtime_path)
+        .update(
+            id,
+            name,
+            url,
+            r#type,
+            headers,
+            env_vars,
+            runtime_path,
+            tool_policies,
+        )
         .map_err(|e| AppError::Mcp(e.to_string()))
 }
 
@@ -65,9 +87,74 @@ pub fn update_mcp_server_status(
 pub fn delete_mcp_server_connection(
     id: String,
     state: State<'_, AppState>,
+    mcp_state: State<'_, MCPClientState>,
 ) -> Result<(), AppError> {
+    let supervisor = mcp_state.supervisor.clone();
+    let connection_id = id.clone();
+    tauri::async_runtime::spawn(async move { supervisor.stop(&connection_id).await });
+
     state
         .mcp_connection_service
         .delete(id)
         .map_err(|e| AppError::Mcp(e.to_string()))
 }
+
+#[tauri::command]
+pub fn preview_mcp_server_import(
+    content: String,
+    state: State<'_, AppState>,
+) -> Result<MCPServerImportPreview, AppError> {
+    state
+        .mcp_connection_service
+        .preview_import(&content)
+        .map_err(|e| AppError::Mcp(e.to_string()))
+}
+
+#[tauri::command]
+pub fn import_mcp_server_connections(
+    content: String,
+    names: Option<Vec<String>>,
+    strategy: Option<MCPImportConflictStrategy>,
+    state: State<'_, AppState>,
+) -> Result<MCPServerImportResult, AppError> {
+    state
+        .mcp_connection_service
+        .import(&content, names, strategy.unwrap_or_default())
+        .map_err(|e| AppError::Mcp(e.to_string()))
+}
+
+#[tauri::command]
+pub fn export_mcp_server_connections(
+    ids: Option<Vec<String>>,
+    state: State<'_, AppState>,
+) -> Result<String, AppError> {
+    state
+        .mcp_connection_service
+        .export(ids)
+        .map_err(|e| AppError::Mcp(e.to_string()))
+}
+
+#[tauri::command]
+pub async fn authorize_mcp_server_connection(
+    app: tauri::AppHandle,
+    id: String,
+    client_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<MCPServerConnection, AppError> {
+    state
+        .mcp_connection_service
+        .authorize(&app, &id, client_id)
+        .await
+        .map_err(|e| AppError::Mcp(e.to_string()))
+}
+
+#[tauri::command]
+pub fn clear_mcp_server_authorization(
+    id: String,
+    state: State<'_, AppState>,
+) -> Result<(), AppError> {
+    state
+        .mcp_connection_service
+        .clear_authorization(&id)
+        .map_err(|e| AppError::Mcp(e.to_string()))
+}
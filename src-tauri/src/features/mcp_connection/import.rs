@@ -0,0 +1,449 @@
+use super::models::{MCPImportConflict, MCPServerConnection, MCPServerImportCandidate};
+use crate::error::AppError;
+use serde_json::{json, Map, Value};
+
+/// A server definition parsed from a Claude Desktop / Cursor / VS Code config,
+/// already mapped onto the fields of `MCPServerConnection`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportedServer {
+    pub name: String,
+    pub url: String,
+    pub r#type: String,
+    pub headers: String,
+    pub env_vars: Option<String>,
+    pub runtime_path: Option<String>,
+}
+
+/// Parse an `mcpServers` style config.
+///
+/// Accepted layouts:
+/// - `{"mcpServers": {...}}` (Claude Desktop, Cursor, Windsurf)
+/// - `{"servers": {...}}` (VS Code `.vscode/mcp.json`)
+/// - `{"mcp": {"servers": {...}}}` (VS Code `settings.json`)
+///
+/// Returns the parsed servers and human readable warnings for entries that
+/// were skipped or only partially understood.
+pub fn parse_mcp_config(content: &str) -> Result<(Vec<ImportedServer>, Vec<String>), AppError> {
+    let root: Value = serde_json::from_str(content)
+        .map_err(|e| AppError::Validation(format!("Invalid MCP config JSON: {e}")))?;
+
+    let servers = root
+        .get("mcpServers")
+        .or_else(|| root.get("servers"))
+        .or_else(|| root.get("mcp").and_then(|m| m.get("servers")))
+        .and_then(Value::as_object)
+        .ok_or_else(|| {
+            AppError::Validation(
+                "No 'mcpServers' or 'servers' object found in MCP config".to_string(),
+            )
+        })?;
+
+    let mut imported = Vec::new();
+    let mut warnings = Vec::new();
+
+    for (name, entry) in servers {
+        let Some(entry) = entry.as_object() else {
+            warnings.push(format!(
+                "Skipped '{name}': server definition is not an object"
+            ));
+            continue;
+        };
+        match parse_server_entry(name, entry, &mut warnings) {
+            Ok(server) => imported.push(server),
+            Err(reason) => warnings.push(format!("Skipped '{name}': {reason}")),
+        }
+    }
+
+    Ok((imported, warnings))
+}
+
+fn parse_server_entry(
+    name: &str,
+    entry: &Map<String, Value>,
+    warnings: &mut Vec<String>,
+) -> Result<ImportedServer, String> {
+    let declared_type = entry.get("type").and_then(Value::as_str);
+    let url = entry
+        .get("url")
+        .or_else(|| entry.get("serverUrl"))
+        .and_then(Value::as_str);
+    let command = entry.get("command").and_then(Value::as_str);
+
+    if entry.contains_key("envFile") {
+        warnings.push(format!(
+            "'{name}': envFile is not supported, set the variables explicitly"
+        ));
+    }
+
+    let r#type = match (declared_type, url, command) {
+        (Some("stdio"), _, _) | (None, None, Some(_)) => "stdio",
+        (Some("sse"), _, _) => "sse",
+        (Some("http" | "streamable-http" | "streamableHttp" | "http-streamable"), _, _) => {
+            "http-streamable"
+        }
+        (Some(other), _, _) => return Err(format!("unsupported transport type '{other}'")),
+        (None, Some(url), _) => {
+            if url.trim_end_matches('/').ends_with("/sse") {
+                "sse"
+            } else {
+                "http-streamable"
+            }
+        }
+        (None, None, None) => return Err("neither 'command' nor 'url' is set".to_string()),
+    };
+
+    let server = if r#type == "stdio" {
+        let command = command.ok_or_else(|| "stdio server has no 'command'".to_string())?;
+        let args = string_array(entry.get("args"));
+        let (url, runtime_path) = map_stdio_command(command, &args);
+        let env = string_map(entry.get("env"));
+        ImportedServer {
+            name: name.to_string(),
+            url,
+            r#type: r#type.to_string(),
+            headers: String::new(),
+            env_vars: env.map(|m| Value::Object(m).to_string()),
+            runtime_path,
+        }
+    } else {
+        let url = url.ok_or_else(|| format!("{type} server has no 'url'"))?;
+        let headers = string_map(entry.get("headers"))
+            .map(|m| Value::Object(m).to_string())
+            .unwrap_or_default();
+        ImportedServer {
+            name: name.to_string(),
+            url: url.to_string(),
+            r#type: r#type.to_string(),
+            headers,
+            env_vars: None,
+            runtime_path: None,
+        }
+    };
+
+    let raw = format!(
+        "{} {} {}",
+        server.url,
+        server.headers,
+        server.env_vars.as_deref().unwrap_or("")
+    );
+    if raw.contains("${input:") {
+        warnings.push(format!(
+            "'{name}': VS Code input placeholders are not resolved, edit the values after import"
+        ));
+    }
+
+    Ok(server)
+}
+
+/// Map a `command` + `args` pair onto the stdio `url` string and an optional
+/// `runtime_path`.
+///
+/// Absolute interpreter paths (e.g. `/usr/bin/python3`) become the bare
+/// command with the path kept as `runtime_path`, and `uvx` is rewritten to
+/// `uv tool run` so that both resolve through the sandboxed runtimes.
+fn map_stdio_command(command: &str, args: &[String]) -> (String, Option<String>) {
+    let file_name = command.rsplit(['/', '\\']).next().unwrap_or(command);
+    let stem = file_name
+        .strip_suffix(".exe")
+        .or_else(|| file_name.strip_suffix(".cmd"))
+        .unwrap_or(file_name);
+    let is_path = file_name.len() != command.len();
+
+    let (parts, runtime_path): (Vec<String>, Option<String>) = match stem {
+        "uvx" => (
+            ["uv", "tool", "run"]
+                .into_iter()
+                .map(str::to_string)
+                .chain(args.iter().cloned())
+                .collect(),
+            None,
+        ),
+        "python" | "python3" | "node" if is_path => (
+            std::iter::once(stem.to_string())
+                .chain(args.iter().cloned())
+                .collect(),
+            Some(command.to_string()),
+        ),
+        "uv" | "npx" | "npm" => (
+            std::iter::once(stem.to_string())
+                .chain(args.iter().cloned())
+                .collect(),
+            None,
+        ),
+        _ => (
+            std::iter::once(command.to_string())
+                .chain(args.iter().cloned())
+                .collect(),
+            None,
+        ),
+    };
+
+    (shell_words::join(parts), runtime_path)
+}
+
+fn string_array(value: Option<&Value>) -> Vec<String> {
+    value
+        .and_then(Value::as_array)
+        .map(|items| {
+            items
+                .iter()
+                .map(|v| match v {
+                    Value::String(s) => s.clone(),
+                    other => other.to_string(),
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn string_map(value: Option<&Value>) -> Option<Map<String, Value>> {
+    let obj = value.and_then(Value::as_object)?;
+    let map: Map<String, Value> = obj
+        .iter()
+        .map(|(k, v)| {
+            let s = match v {
+                Value::String(s) => s.clone(),
+                other => other.to_string(),
+            };
+            (k.clone(), Value::String(s))
+        })
+        .collect();
+    if map.is_empty() {
+        None
+    } else {
+        Some(map)
+    }
+}
+
+/// Compare imported servers with existing connections by name (case-insensitive).
+pub fn detect_conflicts(
+    servers: Vec<ImportedServer>,
+    existing: &[MCPServerConnection],
+) -> Vec<MCPServerImportCandidate> {
+    servers
+        .into_iter()
+        .map(|s| {
+            let found = existing
+                .iter()
+                .find(|c| c.name.eq_ignore_ascii_case(&s.name));
+            let (conflict, existing_id) = match found {
+                Some(c) if c.url == s.url && c.r#type == s.r#type => {
+                    (MCPImportConflict::Duplicate, Some(c.id.clone()))
+                }
+                Some(c) => (MCPImportConflict::Name, Some(c.id.clone())),
+                None => (MCPImportConflict::None, None),
+            };
+            MCPServerImportCandidate {
+                name: s.name,
+                url: s.url,
+                r#type: s.r#type,
+                headers: s.headers,
+                env_vars: s.env_vars,
+                runtime_path: s.runtime_path,
+                conflict,
+                existing_id,
+            }
+        })
+        .collect()
+}
+
+/// Pick a name that does not collide with any of `taken`, e.g. `github (2)`.
+pub fn unique_name(name: &str, taken: &[String]) -> String {
+    let is_taken = |candidate: &str| taken.iter().any(|t| t.eq_ignore_ascii_case(candidate));
+    if !is_taken(name) {
+        return name.to_string();
+    }
+    let mut n = 2;
+    loop {
+        let candidate = format!("{name} ({n})");
+        if !is_taken(&candidate) {
+            return candidate;
+        }
+        n += 1;
+    }
+}
+
+/// Serialize connections into the Claude Desktop `mcpServers` format.
+pub fn export_mcp_config(connections: &[MCPServerConnection]) -> Result<String, AppError> {
+    let mut servers = Map::new();
+
+    for connection in connections {
+        let entry = if connection.r#type == "stdio" {
+            let parts = shell_words::split(&connection.url).map_err(|e| {
+                AppError::Validation(format!(
+                    "Invalid stdio command for '{}': {e}",
+                    connection.name
+                ))
+            })?;
+            let Some((command, args)) = parts.split_first() else {
+                continue;
+            };
+            let mut entry = json!({ "command": command, "args": args });
+            if let Some(env) = parse_object(connection.env_vars.as_deref()) {
+                entry["env"] = Value::Object(env);
+            }
+            entry
+        } else {
+            let r#type = if connection.r#type == "sse" {
+                "sse"
+            } else {
+                "http"
+            };
+            let mut entry = json!({ "type": r#type, "url": connection.url });
+            if let Some(headers) = parse_object(Some(&connection.headers)) {
+                entry["headers"] = Value::Object(headers);
+            }
+            entry
+        };
+        servers.insert(connection.name.clone(), entry);
+    }
+
+    Ok(serde_json::to_string_pretty(
+        &json!({ "mcpServers": servers }),
+    )?)
+}
+
+fn parse_object(raw: Option<&str>) -> Option<Map<String, Value>> {
+    let raw = raw.filter(|s| !s.trim().is_empty())?;
+    serde_json::from_str::<Value>(raw)
+        .ok()?
+        .as_object()
+        .filter(|m| !m.is_empty())
+        .cloned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn connection(name: &str, url: &str, r#type: &str) -> MCPServerConnection {
+        MCPServerConnection {
+            id: format!("id-{name}"),
+            name: name.to_string(),
+            url: url.to_string(),
+            r#type: r#type.to_string(),
+            headers: String::new(),
+            env_vars: None,
+            runtime_path: None,
+            status: "disconnected".to_string(),
+            tools_json: None,
+            error_message: None,
+            tool_policies: None,
+            auth_status: None,
+            created_at: 0,
+            updated_at: 0,
+        }
+    }
+
+    #[test]
+    fn parses_claude_desktop_config() {
+        let json = r#"{
+            "mcpServers": {
+                "filesystem": {
+                    "command": "npx",
+                    "args": ["-y", "@modelcontextprotocol/server-filesystem", "/Users/me/My Docs"],
+                    "env": { "DEBUG": "1" }
+                },
+                "fetch": { "command": "uvx", "args": ["mcp-server-fetch"] },
+                "remote": { "url": "https://example.com/mcp", "headers": { "Authorization": "Bearer x" } }
+            }
+        }"#;
+        let (servers, warnings) = parse_mcp_config(json).unwrap();
+        assert!(warnings.is_empty());
+        assert_eq!(servers.len(), 3);
+
+        let fs = servers.iter().find(|s| s.name == "filesystem").unwrap();
+        assert_eq!(fs.r#type, "stdio");
+        assert_eq!(
+            shell_words::split(&fs.url).unwrap(),
+            vec![
+                "npx",
+                "-y",
+                "@modelcontextprotocol/server-filesystem",
+                "/Users/me/My Docs"
+            ]
+        );
+        assert_eq!(fs.env_vars.as_deref(), Some(r#"{"DEBUG":"1"}"#));
+
+        let fetch = servers.iter().find(|s| s.name == "fetch").unwrap();
+        assert_eq!(fetch.url, "uv tool run mcp-server-fetch");
+
+        let remote = servers.iter().find(|s| s.name == "remote").unwrap();
+        assert_eq!(remote.r#type, "http-streamable");
+        assert_eq!(remote.headers, r#"{"Authorization":"Bearer x"}"#);
+    }
+
+    #[test]
+    fn parses_vscode_settings_layout() {
+        let json = r#"{
+            "mcp": { "servers": {
+                "events": { "type": "sse", "url": "http://localhost:3000/sse" },
+                "py": { "type": "stdio", "command": "/opt/py/bin/python3", "args": ["server.py"] },
+                "bad": { "type": "websocket", "url": "ws://x" }
+            } }
+        }"#;
+        let (servers, warnings) = parse_mcp_config(json).unwrap();
+        assert_eq!(servers.len(), 2);
+        assert_eq!(warnings.len(), 1);
+
+        let py = servers.iter().find(|s| s.name == "py").unwrap();
+        assert_eq!(py.url, "python3 server.py");
+        assert_eq!(py.runtime_path.as_deref(), Some("/opt/py/bin/python3"));
+        assert_eq!(
+            servers.iter().find(|s| s.name == "events").unwrap().r#type,
+            "sse"
+        );
+    }
+
+    #[test]
+    fn detects_conflicts_and_renames() {
+        let existing = vec![
+            connection("github", "npx -y github-mcp", "stdio"),
+            connection("Remote", "https://old.example.com/mcp", "http-streamable"),
+        ];
+        let (servers, _) = parse_mcp_config(
+            r#"{"mcpServers": {
+                "github": { "command": "npx", "args": ["-y", "github-mcp"] },
+                "remote": { "url": "https://new.example.com/mcp" },
+                "fresh": { "url": "https://fresh.example.com/sse" }
+            }}"#,
+        )
+        .unwrap();
+        let candidates = detect_conflicts(servers, &existing);
+        let conflict_of = |name: &str| {
+            candidates
+                .iter()
+                .find(|c| c.name == name)
+                .map(|c| c.conflict)
+                .unwrap()
+        };
+        assert_eq!(conflict_of("github"), MCPImportConflict::Duplicate);
+        assert_eq!(conflict_of("remote"), MCPImportConflict::Name);
+        assert_eq!(conflict_of("fresh"), MCPImportConflict::None);
+
+        let taken = vec!["remote".to_string(), "remote (2)".to_string()];
+        assert_eq!(unique_name("Remote", &taken), "Remote (3)");
+    }
+
+    #[test]
+    fn export_round_trips() {
+        let mut stdio = connection("fs", "npx -y server-fs '/tmp/a b'", "stdio");
+        stdio.env_vars = Some(r#"{"TOKEN":"t"}"#.to_string());
+        let mut remote = connection("remote", "https://example.com/sse", "sse");
+        remote.headers = r#"{"X-Key":"k"}"#.to_string();
+
+        let exported = export_mcp_config(&[stdio.clone(), remote.clone()]).unwrap();
+        let (servers, warnings) = parse_mcp_config(&exported).unwrap();
+        assert!(warnings.is_empty());
+
+        let fs = servers.iter().find(|s| s.name == "fs").unwrap();
+        assert_eq!(
+            shell_words::split(&fs.url).unwrap(),
+            shell_words::split(&stdio.url).unwrap()
+        );
+        assert_eq!(fs.env_vars, stdio.env_vars);
+        let r = servers.iter().find(|s| s.name == "remote").unwrap();
+        assert_eq!(r.r#type, "sse");
+        assert_eq!(r.headers, remote.headers);
+    }
+}
@@ -1,5 +1,7 @@
 pub mod commands;
+pub mod import;
 pub mod models;
+pub mod oauth;
 pub mod repository;
 pub mod service;
 
@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct MCPServerConnection {
@@ -12,6 +13,162 @@ pub struct MCPServerConnection {
     pub status: String,  // "disconnected" | "connecting" | "connected"
     pub tools_json: Option<String>, // JSON string of tools array
     pub error_message: Option<String>, // Error message if connection failed
+    #[serde(default)]
+    pub tool_policies: Option<String>, // JSON string of MCPToolPolicies
+    #[serde(default)]
+    pub auth_status: Option<String>, // "authorized" | "expired" (OAuth, not persisted in the DB)
     pub created_at: i64,
     pub updated_at: i64,
 }
+
+/// Execution overrides for MCP tools. Unset fields fall back to the
+/// connection defaults, then to the built-in tool behavior.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct MCPToolPolicy {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_secs: Option<u64>,
+    /// Retries for transient transport errors (connection refused, dropped stream, ...).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_retries: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry_backoff_ms: Option<u64>,
+    /// Results longer than this are truncated before being sent to the model.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_result_chars: Option<usize>,
+    /// Always ask for permission before running, regardless of workspace settings.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sensitive: Option<bool>,
+}
+
+impl MCPToolPolicy {
+    /// Field-wise merge where `overrides` wins.
+    #[must_use]
+    pub fn merged_with(&self, overrides: &Self) -> Self {
+        Self {
+            timeout_secs: overrides.timeout_secs.or(self.timeout_secs),
+            max_retries: overrides.max_retries.or(self.max_retries),
+            retry_backoff_ms: overrides.retry_backoff_ms.or(self.retry_backoff_ms),
+            max_result_chars: overrides.max_result_chars.or(self.max_result_chars),
+            sensitive: overrides.sensitive.or(self.sensitive),
+        }
+    }
+}
+
+/// Per-connection policy (`defaults`) plus per-tool overrides keyed by tool name.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct MCPToolPolicies {
+    #[serde(default)]
+    pub defaults: MCPToolPolicy,
+    #[serde(default)]
+    pub tools: HashMap<String, MCPToolPolicy>,
+}
+
+impl MCPToolPolicies {
+    pub fn parse(json: Option<&str>) -> Result<Self, serde_json::Error> {
+        json.map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map_or_else(|| Ok(Self::default()), serde_json::from_str)
+    }
+
+    pub fn for_tool(&self, tool_name: &str) -> MCPToolPolicy {
+        self.tools
+            .get(tool_name)
+            .map_or_else(|| self.defaults.clone(), |t| self.defaults.merged_with(t))
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        let all = std::iter::once(("defaults", &self.defaults))
+            .chain(self.tools.iter().map(|(name, p)| (name.as_str(), p)));
+        for (name, policy) in all {
+            if policy.timeout_secs == Some(0) {
+                return Err(format!("{name}: timeout_secs must be greater than 0"));
+            }
+            if policy.max_result_chars == Some(0) {
+                return Err(format!("{name}: max_result_chars must be greater than 0"));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// How an imported server whose name already exists should be handled.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum MCPImportConflictStrategy {
+    #[default]
+    Skip,
+    Overwrite,
+    Rename,
+}
+
+/// Conflict between an imported server and an existing connection.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum MCPImportConflict {
+    None,
+    /// A connection with the same name but a different definition exists.
+    Name,
+    /// An identical connection (same name, type and url) already exists.
+    Duplicate,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MCPServerImportCandidate {
+    pub name: String,
+    pub url: String,
+    pub r#type: String,
+    pub headers: String,
+    pub env_vars: Option<String>,
+    pub runtime_path: Option<String>,
+    pub conflict: MCPImportConflict,
+    pub existing_id: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MCPServerImportPreview {
+    pub candidates: Vec<MCPServerImportCandidate>,
+    pub warnings: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct MCPServerImportResult {
+    pub created: Vec<MCPServerConnection>,
+    pub updated: Vec<String>,
+    pub skipped: Vec<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tool_policy_overrides_connection_defaults() {
+        let policies = MCPToolPolicies::parse(Some(
+            r#"{
+                "defaults": { "timeout_secs": 30, "max_retries": 2 },
+                "tools": { "scrape": { "timeout_secs": 300, "max_result_chars": 20000 } }
+            }"#,
+        ))
+        .unwrap();
+
+        let scrape = policies.for_tool("scrape");
+        assert_eq!(scrape.timeout_secs, Some(300));
+        assert_eq!(scrape.max_retries, Some(2));
+        assert_eq!(scrape.max_result_chars, Some(20000));
+
+        let other = policies.for_tool("calc");
+        assert_eq!(other.timeout_secs, Some(30));
+        assert_eq!(other.max_result_chars, None);
+
+        assert_eq!(
+            MCPToolPolicies::parse(None).unwrap(),
+            MCPToolPolicies::default()
+        );
+        assert!(
+            MCPToolPolicies::parse(Some(r#"{"tools":{"x":{"timeout_secs":0}}}"#))
+                .unwrap()
+                .validate()
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,133 @@
+use super::models::{AuthorizationServerMetadata, ProtectedResourceMetadata};
+use crate::error::AppError;
+use reqwest::Client;
+use url::Url;
+
+/// Canonical resource identifier for an MCP server URL (RFC 8707):
+/// lowercase scheme and host, no fragment or query, no trailing slash.
+pub fn canonical_resource(server_url: &str) -> Result<String, AppError> {
+    let mut url = Url::parse(server_url)
+        .map_err(|e| AppError::Validation(format!("Invalid MCP server URL '{server_url}': {e}")))?;
+    url.set_fragment(None);
+    url.set_query(None);
+    let resource = url.to_string();
+    Ok(resource.trim_end_matches('/').to_string())
+}
+
+/// Build `{origin}/.well-known/{suffix}{path}` as described by RFC 8414 / RFC 9728.
+fn well_known_url(base: &Url, suffix: &str, with_path: bool) -> String {
+    let origin = base.origin().ascii_serialization();
+    let path = base.path().trim_end_matches('/');
+    if with_path && !path.is_empty() {
+        format!("{origin}/.well-known/{suffix}{path}")
+    } else {
+        format!("{origin}/.well-known/{suffix}")
+    }
+}
+
+async fn fetch_json<T: serde::de::DeserializeOwned>(
+    client: &Client,
+    url: &str,
+) -> Result<Option<T>, AppError> {
+    let response = client
+        .get(url)
+        .header(
+            "MCP-Protocol-Version",
+            rust_mcp_sdk::schema::LATEST_PROTOCOL_VERSION,
+        )
+        .send()
+        .await?;
+    if !response.status().is_success() {
+        return Ok(None);
+    }
+    Ok(response.json::<T>().await.ok())
+}
+
+/// Discover the authorization server for an MCP server.
+///
+/// Follows the MCP authorization spec: protected resource metadata first,
+/// then authorization server metadata (RFC 8414, then OIDC discovery), and
+/// finally the legacy default endpoints relative to the server origin.
+pub async fn discover(
+    client: &Client,
+    server_url: &str,
+) -> Result<AuthorizationServerMetadata, AppError> {
+    let server = Url::parse(server_url)
+        .map_err(|e| AppError::Validation(format!("Invalid MCP server URL '{server_url}': {e}")))?;
+
+    let mut issuer: Option<Url> = None;
+    for with_path in [true, false] {
+        let url = well_known_url(&server, "oauth-protected-resource", with_path);
+        if let Some(meta) = fetch_json::<ProtectedResourceMetadata>(client, &url).await? {
+            issuer = meta
+                .authorization_servers
+                .first()
+                .and_then(|s| Url::parse(s).ok());
+            if issuer.is_some() {
+                break;
+            }
+        }
+    }
+
+    let issuer_url = issuer.clone().unwrap_or_else(|| server.clone());
+    let candidates = [
+        well_known_url(&issuer_url, "oauth-authorization-server", true),
+        well_known_url(&issuer_url, "openid-configuration", true),
+        format!(
+            "{}/.well-known/openid-configuration",
+            issuer_url.as_str().trim_end_matches('/')
+        ),
+        well_known_url(&issuer_url, "oauth-authorization-server", false),
+    ];
+    for url in candidates {
+        if let Some(meta) = fetch_json::<AuthorizationServerMetadata>(client, &url).await? {
+            return Ok(meta);
+        }
+    }
+
+    if issuer.is_some() {
+        return Err(AppError::Mcp(format!(
+            "Authorization server metadata not found for {issuer_url}"
+        )));
+    }
+
+    let origin = server.origin().ascii_serialization();
+    Ok(AuthorizationServerMetadata {
+        issuer: Some(origin.clone()),
+        authorization_endpoint: format!("{origin}/authorize"),
+        token_endpoint: format!("{origin}/token"),
+        registration_endpoint: Some(format!("{origin}/register")),
+        scopes_supported: None,
+        code_challenge_methods_supported: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn canonicalizes_resource() {
+        assert_eq!(
+            canonical_resource("HTTPS://Example.com/mcp/?x=1#frag").unwrap(),
+            "https://example.com/mcp"
+        );
+        assert_eq!(
+            canonical_resource("https://example.com").unwrap(),
+            "https://example.com"
+        );
+    }
+
+    #[test]
+    fn builds_well_known_urls() {
+        let base = Url::parse("https://auth.example.com/tenant1/").unwrap();
+        assert_eq!(
+            well_known_url(&base, "oauth-authorization-server", true),
+            "https://auth.example.com/.well-known/oauth-authorization-server/tenant1"
+        );
+        assert_eq!(
+            well_known_url(&base, "oauth-authorization-server", false),
+            "https://auth.example.com/.well-known/oauth-authorization-server"
+        );
+    }
+}
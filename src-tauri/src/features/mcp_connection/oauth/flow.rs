@@ -0,0 +1,227 @@
+use super::discovery::{canonical_resource, discover};
+use super::loopback::LoopbackRedirect;
+use super::models::{
+    AuthorizationServerMetadata, OAuthClient, OAuthTokens, StoredCredentials, TokenResponse,
+};
+use super::pkce::{random_token, PkcePair};
+use crate::error::AppError;
+use reqwest::Client;
+use serde_json::json;
+use std::time::Duration;
+
+const CLIENT_NAME: &str = "Cogito Studio";
+const AUTHORIZE_TIMEOUT: Duration = Duration::from_secs(300);
+
+fn now_ms() -> i64 {
+    chrono::Utc::now().timestamp_millis()
+}
+
+/// Register a public client (RFC 7591) for the loopback redirect URI.
+pub async fn register_client(
+    client: &Client,
+    metadata: &AuthorizationServerMetadata,
+    redirect_uri: &str,
+) -> Result<OAuthClient, AppError> {
+    let endpoint = metadata.registration_endpoint.as_deref().ok_or_else(|| {
+        AppError::Mcp(
+            "Authorization server does not support dynamic client registration; provide a client id"
+                .to_string(),
+        )
+    })?;
+
+    let response = client
+        .post(endpoint)
+        .json(&json!({
+            "client_name": CLIENT_NAME,
+            "redirect_uris": [redirect_uri],
+            "grant_types": ["authorization_code", "refresh_token"],
+            "response_types": ["code"],
+            "token_endpoint_auth_method": "none",
+        }))
+        .send()
+        .await?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(AppError::Mcp(format!(
+            "Dynamic client registration failed with status {status}: {body}"
+        )));
+    }
+    Ok(response.json::<OAuthClient>().await?)
+}
+
+pub fn build_authorization_url(
+    metadata: &AuthorizationServerMetadata,
+    oauth_client: &OAuthClient,
+    redirect_uri: &str,
+    resource: &str,
+    pkce: &PkcePair,
+    state: &str,
+) -> Result<String, AppError> {
+    let mut url = url::Url::parse(&metadata.authorization_endpoint).map_err(|e| {
+        AppError::Mcp(format!(
+            "Invalid authorization endpoint '{}': {e}",
+            metadata.authorization_endpoint
+        ))
+    })?;
+    {
+        let mut query = url.query_pairs_mut();
+        query
+            .append_pair("response_type", "code")
+            .append_pair("client_id", &oauth_client.client_id)
+            .append_pair("redirect_uri", redirect_uri)
+            .append_pair("code_challenge", &pkce.challenge)
+            .append_pair("code_challenge_method", "S256")
+            .append_pair("state", state)
+            .append_pair("resource", resource);
+        if let Some(scopes) = metadata.scopes_supported.as_ref().filter(|s| !s.is_empty()) {
+            query.append_pair("scope", &scopes.join(" "));
+        }
+    }
+    Ok(url.into())
+}
+
+async fn request_tokens(
+    client: &Client,
+    metadata: &AuthorizationServerMetadata,
+    oauth_client: &OAuthClient,
+    mut form: Vec<(&str, String)>,
+) -> Result<TokenResponse, AppError> {
+    form.push(("client_id", oauth_client.client_id.clone()));
+    if let Some(secret) = &oauth_client.client_secret {
+        form.push(("client_secret", secret.clone()));
+    }
+
+    let response = client
+        .post(&metadata.token_endpoint)
+        .header("Accept", "application/json")
+        .form(&form)
+        .send()
+        .await?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(AppError::Mcp(format!(
+            "Token request failed with status {status}: {body}"
+        )));
+    }
+    Ok(response.json::<TokenResponse>().await?)
+}
+
+fn into_tokens(response: TokenResponse, previous_refresh: Option<String>) -> OAuthTokens {
+    OAuthTokens {
+        access_token: response.access_token,
+        refresh_token: response.refresh_token.or(previous_refresh),
+        expires_at: response.expires_in.map(|secs| now_ms() + secs * 1000),
+        scope: response.scope,
+    }
+}
+
+pub async fn exchange_code(
+    client: &Client,
+    credentials: &StoredCredentials,
+    code: &str,
+    redirect_uri: &str,
+    verifier: &str,
+) -> Result<OAuthTokens, AppError> {
+    let response = request_tokens(
+        client,
+        &credentials.metadata,
+        &credentials.client,
+        vec![
+            ("grant_type", "authorization_code".to_string()),
+            ("code", code.to_string()),
+            ("redirect_uri", redirect_uri.to_string()),
+            ("code_verifier", verifier.to_string()),
+            ("resource", credentials.resource.clone()),
+        ],
+    )
+    .await?;
+    Ok(into_tokens(response, None))
+}
+
+pub async fn refresh_tokens(
+    client: &Client,
+    credentials: &StoredCredentials,
+) -> Result<OAuthTokens, AppError> {
+    let refresh_token = credentials
+        .tokens
+        .as_ref()
+        .and_then(|t| t.refresh_token.clone())
+        .ok_or_else(|| AppError::Mcp("No refresh token available".to_string()))?;
+
+    let response = request_tokens(
+        client,
+        &credentials.metadata,
+        &credentials.client,
+        vec![
+            ("grant_type", "refresh_token".to_string()),
+            ("refresh_token", refresh_token.clone()),
+            ("resource", credentials.resource.clone()),
+        ],
+    )
+    .await?;
+    Ok(into_tokens(response, Some(refresh_token)))
+}
+
+/// Run the full authorization-code-with-PKCE flow for `server_url`.
+///
+/// `open_url` is called with the authorization URL (normally opens the
+/// system browser); the redirect is received on a loopback listener.
+pub async fn authorize<F>(
+    client: &Client,
+    server_url: &str,
+    client_id: Option<String>,
+    open_url: F,
+) -> Result<StoredCredentials, AppError>
+where
+    F: FnOnce(&str) -> Result<(), AppError>,
+{
+    let resource = canonical_resource(server_url)?;
+    let metadata = discover(client, server_url).await?;
+    if let Some(methods) = &metadata.code_challenge_methods_supported {
+        if !methods.iter().any(|m| m == "S256") {
+            return Err(AppError::Mcp(
+                "Authorization server does not support PKCE with S256".to_string(),
+            ));
+        }
+    }
+
+    let redirect = LoopbackRedirect::bind().await?;
+    let redirect_uri = redirect.redirect_uri();
+
+    let oauth_client = match client_id.filter(|id| !id.trim().is_empty()) {
+        Some(client_id) => OAuthClient {
+            client_id,
+            client_secret: None,
+        },
+        None => register_client(client, &metadata, &redirect_uri).await?,
+    };
+
+    let pkce = PkcePair::generate();
+    let state = random_token(24);
+    let auth_url = build_authorization_url(
+        &metadata,
+        &oauth_client,
+        &redirect_uri,
+        &resource,
+        &pkce,
+        &state,
+    )?;
+
+    open_url(&auth_url)?;
+    let code = redirect.wait_for_code(&state, AUTHORIZE_TIMEOUT).await?;
+
+    let mut credentials = StoredCredentials {
+        resource,
+        metadata,
+        client: oauth_client,
+        tokens: None,
+        needs_reauth: false,
+    };
+    credentials.tokens =
+        Some(exchange_code(client, &credentials, &code, &redirect_uri, &pkce.verifier).await?);
+    Ok(credentials)
+}
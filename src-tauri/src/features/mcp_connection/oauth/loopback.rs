@@ -0,0 +1,141 @@
+use crate::error::AppError;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+const CALLBACK_PATH: &str = "/callback";
+const SUCCESS_PAGE: &str = "<!doctype html><html><body style=\"font-family:sans-serif\">\
+<h3>Authorization complete</h3><p>You can close this window and return to the app.</p></body></html>";
+const FAILURE_PAGE: &str = "<!doctype html><html><body style=\"font-family:sans-serif\">\
+<h3>Authorization failed</h3><p>Return to the app for details.</p></body></html>";
+
+/// One-shot HTTP listener on 127.0.0.1 that receives the authorization
+/// redirect (RFC 8252 loopback interface redirection).
+pub struct LoopbackRedirect {
+    listener: TcpListener,
+    port: u16,
+}
+
+impl LoopbackRedirect {
+    pub async fn bind() -> Result<Self, AppError> {
+        let listener = TcpListener::bind(("127.0.0.1", 0)).await?;
+        let port = listener.local_addr()?.port();
+        Ok(Self { listener, port })
+    }
+
+    pub fn redirect_uri(&self) -> String {
+        format!("http://127.0.0.1:{}{CALLBACK_PATH}", self.port)
+    }
+
+    /// Wait for the redirect carrying `expected_state` and return the
+    /// authorization code. Requests to other paths (e.g. favicon) are ignored.
+    pub async fn wait_for_code(
+        self,
+        expected_state: &str,
+        timeout: Duration,
+    ) -> Result<String, AppError> {
+        tokio::time::timeout(timeout, self.accept_loop(expected_state))
+            .await
+            .map_err(|_| AppError::Mcp("Timed out waiting for OAuth authorization".to_string()))?
+    }
+
+    async fn accept_loop(self, expected_state: &str) -> Result<String, AppError> {
+        loop {
+            let (mut stream, _) = self.listener.accept().await?;
+
+            let mut buf = Vec::new();
+            let mut chunk = [0u8; 2048];
+            while !buf.windows(4).any(|w| w == b"\r\n\r\n") && buf.len() < 16 * 1024 {
+                let n = stream.read(&mut chunk).await?;
+                if n == 0 {
+                    break;
+                }
+                buf.extend_from_slice(&chunk[..n]);
+            }
+
+            let request = String::from_utf8_lossy(&buf);
+            let target = request
+                .lines()
+                .next()
+                .and_then(|line| line.split_whitespace().nth(1))
+                .unwrap_or("/");
+
+            let Some(result) = parse_callback(target, expected_state) else {
+                let _ = stream
+                    .write_all(
+                        b"HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+                    )
+                    .await;
+                continue;
+            };
+
+            let page = if result.is_ok() {
+                SUCCESS_PAGE
+            } else {
+                FAILURE_PAGE
+            };
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: text/html; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{page}",
+                page.len()
+            );
+            let _ = stream.write_all(response.as_bytes()).await;
+            let _ = stream.shutdown().await;
+            return result;
+        }
+    }
+}
+
+/// Parse the redirect request target. Returns `None` for unrelated paths.
+fn parse_callback(target: &str, expected_state: &str) -> Option<Result<String, AppError>> {
+    let url = url::Url::parse(&format!("http://127.0.0.1{target}")).ok()?;
+    if url.path() != CALLBACK_PATH {
+        return None;
+    }
+
+    let param = |name: &str| {
+        url.query_pairs()
+            .find(|(k, _)| k == name)
+            .map(|(_, v)| v.into_owned())
+    };
+
+    if let Some(error) = param("error") {
+        let description = param("error_description").unwrap_or_default();
+        return Some(Err(AppError::Mcp(format!(
+            "Authorization denied: {error} {description}"
+        ))));
+    }
+    if param("state").as_deref() != Some(expected_state) {
+        return Some(Err(AppError::Mcp(
+            "OAuth state mismatch in authorization response".to_string(),
+        )));
+    }
+    Some(
+        param("code").ok_or_else(|| {
+            AppError::Mcp("Authorization response did not contain a code".to_string())
+        }),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_callback_target() {
+        assert_eq!(
+            parse_callback("/callback?code=abc&state=s1", "s1")
+                .unwrap()
+                .unwrap(),
+            "abc"
+        );
+        assert!(parse_callback("/favicon.ico", "s1").is_none());
+        assert!(parse_callback("/callback?code=abc&state=other", "s1")
+            .unwrap()
+            .is_err());
+        assert!(
+            parse_callback("/callback?error=access_denied&state=s1", "s1")
+                .unwrap()
+                .is_err()
+        );
+    }
+}
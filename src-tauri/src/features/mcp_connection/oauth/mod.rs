@@ -0,0 +1,290 @@
+//! OAuth 2.1 authorization for remote MCP servers.
+//!
+//! Follows the MCP authorization spec: metadata discovery, dynamic client
+//! registration, authorization code with PKCE over a loopback redirect, and
+//! refresh tokens.
+
+pub mod discovery;
+pub mod flow;
+pub mod loopback;
+pub mod models;
+pub mod pkce;
+pub mod store;
+
+pub use models::McpAuthStatus;
+pub use store::OAuthTokenStore;
+
+use crate::error::AppError;
+use discovery::canonical_resource;
+use reqwest::Client;
+use std::sync::OnceLock;
+use std::time::Duration;
+use tauri::AppHandle;
+use tauri_plugin_opener::OpenerExt;
+
+/// Refresh access tokens this long before they expire.
+const REFRESH_SKEW_MS: i64 = 60_000;
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Serializes refreshes so concurrent tool calls do not race on a rotating refresh token.
+fn refresh_lock() -> &'static tokio::sync::Mutex<()> {
+    static LOCK: OnceLock<tokio::sync::Mutex<()>> = OnceLock::new();
+    LOCK.get_or_init(|| tokio::sync::Mutex::new(()))
+}
+
+fn http_client() -> Result<Client, AppError> {
+    Client::builder()
+        .timeout(REQUEST_TIMEOUT)
+        .build()
+        .map_err(|e| AppError::Generic(e.to_string()))
+}
+
+pub struct McpOAuthService;
+
+impl McpOAuthService {
+    /// Run the interactive authorization flow in the system browser and store the tokens.
+    pub async fn authorize(
+        app: &AppHandle,
+        server_url: &str,
+        client_id: Option<String>,
+    ) -> Result<(), AppError> {
+        let store = OAuthTokenStore::from_app(app)?;
+        let client = http_client()?;
+        let credentials = flow::authorize(&client, server_url, client_id, |url| {
+            app.opener()
+                .open_url(url, None::<&str>)
+                .map_err(|e| AppError::Generic(format!("Failed to open browser: {e}")))
+        })
+        .await?;
+        store.save(&credentials)
+    }
+
+    /// `Authorization` header value for `server_url`, refreshing the access
+    /// token first when it is about to expire. Returns `None` for servers
+    /// that were never authorized.
+    pub async fn authorization_header(
+        app: &AppHandle,
+        server_url: &str,
+    ) -> Result<Option<String>, AppError> {
+        let store = OAuthTokenStore::from_app(app)?;
+        Self::authorization_header_with(&store, &http_client()?, server_url).await
+    }
+
+    pub async fn authorization_header_with(
+        store: &OAuthTokenStore,
+        client: &Client,
+        server_url: &str,
+    ) -> Result<Option<String>, AppError> {
+        let resource = canonical_resource(server_url)?;
+        let _guard = refresh_lock().lock().await;
+
+        let Some(mut credentials) = store.load(&resource)? else {
+            return Ok(None);
+        };
+        let Some(tokens) = credentials.tokens.clone() else {
+            return Ok(None);
+        };
+        if credentials.needs_reauth {
+            return Err(Self::reauth_error(&resource));
+        }
+
+        let now = chrono::Utc::now().timestamp_millis();
+        if !tokens.expires_within(now, REFRESH_SKEW_MS) {
+            return Ok(Some(format!("Bearer {}", tokens.access_token)));
+        }
+
+        match flow::refresh_tokens(client, &credentials).await {
+            Ok(refreshed) => {
+                let header = format!("Bearer {}", refreshed.access_token);
+                credentials.tokens = Some(refreshed);
+                store.save(&credentials)?;
+                Ok(Some(header))
+            }
+            Err(e) => {
+                tracing::warn!("OAuth token refresh for {resource} failed: {e}");
+                credentials.needs_reauth = true;
+                store.save(&credentials)?;
+                Err(Self::reauth_error(&resource))
+            }
+        }
+    }
+
+    fn reauth_error(resource: &str) -> AppError {
+        AppError::Mcp(format!(
+            "OAuth authorization for {resource} has expired. Authorize the server again."
+        ))
+    }
+
+    /// Authorization state for `server_url`, `None` when it has no stored tokens.
+    pub fn status(store: &OAuthTokenStore, server_url: &str) -> Option<McpAuthStatus> {
+        let resource = canonical_resource(server_url).ok()?;
+        let credentials = store.load(&resource).ok()??;
+        let tokens = credentials.tokens.as_ref()?;
+        let now = chrono::Utc::now().timestamp_millis();
+        let expired = tokens.expires_within(now, 0) && tokens.refresh_token.is_none();
+        if credentials.needs_reauth || expired {
+            Some(McpAuthStatus::Expired)
+        } else {
+            Some(McpAuthStatus::Authorized)
+        }
+    }
+
+    pub fn clear(store: &OAuthTokenStore, server_url: &str) -> Result<(), AppError> {
+        store.delete(&canonical_resource(server_url)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    /// Minimal authorization server: metadata, registration, authorize and token endpoints.
+    async fn start_mock_auth_server() -> String {
+        let listener = TcpListener::bind(("127.0.0.1", 0)).await.unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        let challenge: Arc<Mutex<Option<String>>> = Arc::default();
+        let server_base = base.clone();
+
+        tokio::spawn(async move {
+            loop {
+                let Ok((mut stream, _)) = listener.accept().await else {
+                    break;
+                };
+                let base = server_base.clone();
+                let challenge = challenge.clone();
+                tokio::spawn(async move {
+                    let mut buf = Vec::new();
+                    let mut chunk = [0u8; 4096];
+                    let (head, body) = loop {
+                        let n = stream.read(&mut chunk).await.unwrap();
+                        buf.extend_from_slice(&chunk[..n]);
+                        let text = String::from_utf8_lossy(&buf).to_string();
+                        if let Some(idx) = text.find("\r\n\r\n") {
+                            let head = text[..idx].to_string();
+                            let len = head
+                                .lines()
+                                .find_map(|l| {
+                                    l.to_ascii_lowercase()
+                                        .strip_prefix("content-length:")
+                                        .map(|v| v.trim().parse::<usize>().unwrap())
+                                })
+                                .unwrap_or(0);
+                            if text.len() >= idx + 4 + len || n == 0 {
+                                break (head, text[idx + 4..].to_string());
+                            }
+                        }
+                    };
+                    let target = head.split_whitespace().nth(1).unwrap().to_string();
+                    let url = url::Url::parse(&format!("{base}{target}")).unwrap();
+                    let query: HashMap<String, String> = url.query_pairs().into_owned().collect();
+                    let form: HashMap<String, String> =
+                        url::form_urlencoded::parse(body.as_bytes())
+                            .into_owned()
+                            .collect();
+
+                    let (status, location, json) = match url.path() {
+                        "/.well-known/oauth-protected-resource" => (
+                            "200 OK",
+                            None,
+                            format!(r#"{{"authorization_servers":["{base}"]}}"#),
+                        ),
+                        "/.well-known/oauth-authorization-server" => (
+                            "200 OK",
+                            None,
+                            format!(
+                                r#"{{"issuer":"{base}","authorization_endpoint":"{base}/authorize","token_endpoint":"{base}/token","registration_endpoint":"{base}/register","code_challenge_methods_supported":["S256"]}}"#
+                            ),
+                        ),
+                        "/register" => ("201 Created", None, r#"{"client_id":"client-1"}"#.into()),
+                        "/authorize" => {
+                            *challenge.lock().unwrap() = query.get("code_challenge").cloned();
+                            let location = format!(
+                                "{}?code=code-1&state={}",
+                                query["redirect_uri"], query["state"]
+                            );
+                            ("302 Found", Some(location), String::new())
+                        }
+                        "/token" => match form.get("grant_type").map(String::as_str) {
+                            Some("authorization_code") => {
+                                let expected = challenge.lock().unwrap().clone();
+                                let actual = pkce::challenge_for(&form["code_verifier"]);
+                                if form["code"] == "code-1" && expected == Some(actual) {
+                                    ("200 OK", None, r#"{"access_token":"access-1","token_type":"Bearer","refresh_token":"refresh-1","expires_in":30}"#.into())
+                                } else {
+                                    ("400 Bad Request", None, r#"{"error":"invalid_grant"}"#.into())
+                                }
+                            }
+                            Some("refresh_token") if form["refresh_token"] == "refresh-1" => (
+                                "200 OK",
+                                None,
+                                r#"{"access_token":"access-2","token_type":"Bearer","expires_in":3600}"#.into(),
+                            ),
+                            _ => ("400 Bad Request", None, r#"{"error":"invalid_grant"}"#.into()),
+                        },
+                        _ => ("404 Not Found", None, String::new()),
+                    };
+
+                    let location = location
+                        .map(|l| format!("Location: {l}\r\n"))
+                        .unwrap_or_default();
+                    let response = format!(
+                        "HTTP/1.1 {status}\r\n{location}Content-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{json}",
+                        json.len()
+                    );
+                    let _ = stream.write_all(response.as_bytes()).await;
+                    let _ = stream.shutdown().await;
+                });
+            }
+        });
+
+        base
+    }
+
+    #[tokio::test]
+    async fn authorizes_and_refreshes_against_mock_server() {
+        let base = start_mock_auth_server().await;
+        let server_url = format!("{base}/mcp");
+        let client = Client::new();
+
+        let credentials = flow::authorize(&client, &server_url, None, |auth_url| {
+            // Stand in for the browser: follow the authorize redirect to the loopback listener.
+            let auth_url = auth_url.to_string();
+            tokio::spawn(async move {
+                let _ = reqwest::get(auth_url).await;
+            });
+            Ok(())
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(credentials.client.client_id, "client-1");
+        let tokens = credentials.tokens.clone().unwrap();
+        assert_eq!(tokens.access_token, "access-1");
+        assert_eq!(tokens.refresh_token.as_deref(), Some("refresh-1"));
+
+        let dir = tempfile::tempdir().unwrap();
+        let store = OAuthTokenStore::new(dir.path().to_path_buf());
+        store.save(&credentials).unwrap();
+        assert_eq!(
+            McpOAuthService::status(&store, &server_url),
+            Some(McpAuthStatus::Authorized)
+        );
+
+        // expires_in=30s is inside the refresh window, so the header triggers a refresh.
+        let header = McpOAuthService::authorization_header_with(&store, &client, &server_url)
+            .await
+            .unwrap();
+        assert_eq!(header.as_deref(), Some("Bearer access-2"));
+        let stored = store.load(&credentials.resource).unwrap().unwrap();
+        let refreshed = stored.tokens.unwrap();
+        assert_eq!(refreshed.access_token, "access-2");
+        assert_eq!(refreshed.refresh_token.as_deref(), Some("refresh-1"));
+
+        McpOAuthService::clear(&store, &server_url).unwrap();
+        assert!(McpOAuthService::status(&store, &server_url).is_none());
+    }
+}
@@ -0,0 +1,97 @@
+use serde::{Deserialize, Serialize};
+
+/// OAuth 2.0 Protected Resource Metadata (RFC 9728), the subset we use.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProtectedResourceMetadata {
+    #[serde(default)]
+    pub resource: Option<String>,
+    #[serde(default)]
+    pub authorization_servers: Vec<String>,
+}
+
+/// OAuth 2.0 Authorization Server Metadata (RFC 8414), the subset we use.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuthorizationServerMetadata {
+    #[serde(default)]
+    pub issuer: Option<String>,
+    pub authorization_endpoint: String,
+    pub token_endpoint: String,
+    #[serde(default)]
+    pub registration_endpoint: Option<String>,
+    #[serde(default)]
+    pub scopes_supported: Option<Vec<String>>,
+    #[serde(default)]
+    pub code_challenge_methods_supported: Option<Vec<String>>,
+}
+
+/// Client credentials obtained through dynamic client registration (RFC 7591)
+/// or supplied by the user for servers without registration support.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OAuthClient {
+    pub client_id: String,
+    #[serde(default)]
+    pub client_secret: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OAuthTokens {
+    pub access_token: String,
+    #[serde(default)]
+    pub refresh_token: Option<String>,
+    /// Expiry as unix millis; `None` when the server did not send `expires_in`.
+    #[serde(default)]
+    pub expires_at: Option<i64>,
+    #[serde(default)]
+    pub scope: Option<String>,
+}
+
+impl OAuthTokens {
+    /// True when the access token expires within `skew_ms`.
+    pub fn expires_within(&self, now_ms: i64, skew_ms: i64) -> bool {
+        self.expires_at.is_some_and(|at| at - skew_ms <= now_ms)
+    }
+}
+
+/// Raw token endpoint response (RFC 6749 section 5.1).
+#[derive(Debug, Deserialize)]
+pub struct TokenResponse {
+    pub access_token: String,
+    #[serde(default)]
+    pub token_type: Option<String>,
+    #[serde(default)]
+    pub refresh_token: Option<String>,
+    #[serde(default)]
+    pub expires_in: Option<i64>,
+    #[serde(default)]
+    pub scope: Option<String>,
+}
+
+/// Everything persisted for one MCP server, keyed by its canonical resource URL.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoredCredentials {
+    pub resource: String,
+    pub metadata: AuthorizationServerMetadata,
+    pub client: OAuthClient,
+    #[serde(default)]
+    pub tokens: Option<OAuthTokens>,
+    /// Set when a refresh failed and the user has to authorize again.
+    #[serde(default)]
+    pub needs_reauth: bool,
+}
+
+/// Authorization state reported on `MCPServerConnection::auth_status`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum McpAuthStatus {
+    Authorized,
+    Expired,
+}
+
+impl McpAuthStatus {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Authorized => "authorized",
+            Self::Expired => "expired",
+        }
+    }
+}
@@ -0,0 +1,54 @@
+use base64::{engine::general_purpose, Engine as _};
+use sha2::{Digest, Sha256};
+
+/// PKCE verifier/challenge pair (RFC 7636, `S256` method).
+#[derive(Debug, Clone)]
+pub struct PkcePair {
+    pub verifier: String,
+    pub challenge: String,
+}
+
+impl PkcePair {
+    pub fn generate() -> Self {
+        let verifier = random_token(48);
+        let challenge = challenge_for(&verifier);
+        Self {
+            verifier,
+            challenge,
+        }
+    }
+}
+
+/// URL-safe random string of `bytes` bytes taken from v4 UUIDs (OS randomness).
+pub fn random_token(bytes: usize) -> String {
+    let mut buf = Vec::with_capacity(bytes + 16);
+    while buf.len() < bytes {
+        buf.extend_from_slice(uuid::Uuid::new_v4().as_bytes());
+    }
+    buf.truncate(bytes);
+    general_purpose::URL_SAFE_NO_PAD.encode(buf)
+}
+
+pub fn challenge_for(verifier: &str) -> String {
+    general_purpose::URL_SAFE_NO_PAD.encode(Sha256::digest(verifier.as_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_rfc7636_example() {
+        assert_eq!(
+            challenge_for("dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk"),
+            "E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM"
+        );
+    }
+
+    #[test]
+    fn verifier_has_valid_length() {
+        let pair = PkcePair::generate();
+        assert!((43..=128).contains(&pair.verifier.len()));
+        assert_ne!(pair.verifier, PkcePair::generate().verifier);
+    }
+}
@@ -0,0 +1,110 @@
+use super::models::StoredCredentials;
+use crate::error::AppError;
+use sha2::{Digest, Sha256};
+use std::io::Write;
+use std::path::PathBuf;
+use tauri::{AppHandle, Manager};
+
+/// File-backed store for MCP OAuth credentials.
+///
+/// Tokens are kept out of the `SQLite` database (whose rows are sent to the
+/// frontend as-is) in one file per server under `<app_data>/mcp_oauth`,
+/// readable only by the current user on unix.
+pub struct OAuthTokenStore {
+    dir: PathBuf,
+}
+
+impl OAuthTokenStore {
+    pub const fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    pub fn from_app(app: &AppHandle) -> Result<Self, AppError> {
+        let app_data = app.path().app_data_dir().map_err(AppError::Tauri)?;
+        Ok(Self::new(app_data.join("mcp_oauth")))
+    }
+
+    fn path_for(&self, resource: &str) -> PathBuf {
+        let key = hex::encode(Sha256::digest(resource.as_bytes()));
+        self.dir.join(format!("{key}.json"))
+    }
+
+    pub fn load(&self, resource: &str) -> Result<Option<StoredCredentials>, AppError> {
+        let path = self.path_for(resource);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = std::fs::read_to_string(path)?;
+        Ok(Some(serde_json::from_str(&content)?))
+    }
+
+    pub fn save(&self, credentials: &StoredCredentials) -> Result<(), AppError> {
+        std::fs::create_dir_all(&self.dir)?;
+        let path = self.path_for(&credentials.resource);
+        let tmp = path.with_extension("json.tmp");
+
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+        let mut file = options.open(&tmp)?;
+        file.write_all(serde_json::to_string(credentials)?.as_bytes())?;
+        file.sync_all()?;
+        drop(file);
+
+        std::fs::rename(tmp, path)?;
+        Ok(())
+    }
+
+    pub fn delete(&self, resource: &str) -> Result<(), AppError> {
+        let path = self.path_for(resource);
+        if path.exists() {
+            std::fs::remove_file(path)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::models::{AuthorizationServerMetadata, OAuthClient, OAuthTokens};
+    use super::*;
+
+    #[test]
+    fn save_load_delete() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = OAuthTokenStore::new(dir.path().join("oauth"));
+        let creds = StoredCredentials {
+            resource: "https://example.com/mcp".to_string(),
+            metadata: AuthorizationServerMetadata {
+                issuer: None,
+                authorization_endpoint: "https://example.com/authorize".to_string(),
+                token_endpoint: "https://example.com/token".to_string(),
+                registration_endpoint: None,
+                scopes_supported: None,
+                code_challenge_methods_supported: None,
+            },
+            client: OAuthClient {
+                client_id: "abc".to_string(),
+                client_secret: None,
+            },
+            tokens: Some(OAuthTokens {
+                access_token: "at".to_string(),
+                refresh_token: Some("rt".to_string()),
+                expires_at: Some(1),
+                scope: None,
+            }),
+            needs_reauth: false,
+        };
+
+        assert!(store.load(&creds.resource).unwrap().is_none());
+        store.save(&creds).unwrap();
+        let loaded = store.load(&creds.resource).unwrap().unwrap();
+        assert_eq!(loaded.tokens.unwrap().access_token, "at");
+        store.delete(&creds.resource).unwrap();
+        assert!(store.load(&creds.resource).unwrap().is_none());
+    }
+}
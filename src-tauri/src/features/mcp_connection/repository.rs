@@ -17,6 +17,7 @@ pub trait MCPConnectionRepository: Send + Sync {
         headers: Option<&str>,
         env_vars: Option<&str>,
         runtime_path: Option<&str>,
+        tool_policies: Option<&str>,
     ) -> Result<(), AppError>;
     fn update_status(
         &self,
@@ -42,8 +43,8 @@ impl MCPConnectionRepository for SqliteMCPConnectionRepository {
     fn create(&self, connection: &MCPServerConnection) -> Result<(), AppError> {
         let conn = crate::db::get_connection(&self.app)?;
         conn.execute(
-            "INSERT INTO mcp_server_connections (id, name, url, type, headers, env_vars, runtime_path, status, tools_json, error_message, created_at, updated_at) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
-            params![connection.id, connection.name, connection.url, connection.r#type, connection.headers, connection.env_vars, connection.runtime_path, connection.status, connection.tools_json, connection.error_message, connection.created_at, connection.updated_at],
+            "INSERT INTO mcp_server_connections (id, name, url, type, headers, env_vars, runtime_path, status, tools_json, error_message, created_at, updated_at, tool_policies) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
+            params![connection.id, connection.name, connection.url, connection.r#type, connection.headers, connection.env_vars, connection.runtime_path, connection.status, connection.tools_json, connection.error_message, connection.created_at, connection.updated_at, connection.tool_policies],
         )?;
         Ok(())
     }
@@ -51,7 +52,7 @@ impl MCPConnectionRepository for SqliteMCPConnectionRepository {
     fn get_all(&self) -> Result<Vec<MCPServerConnection>, AppError> {
         let conn = crate::db::get_connection(&self.app)?;
         let mut stmt = conn.prepare(
-            "SELECT id, name, url, type, headers, env_vars, runtime_path, status, tools_json, error_message, created_at, updated_at FROM mcp_server_connections ORDER BY created_at DESC"
+            "SELECT id, name, url, type, headers, env_vars, runtime_path, status, tools_json, error_message, created_at, updated_at, tool_policies FROM mcp_server_connections ORDER BY created_at DESC"
         )?;
 
         let connections = stmt
@@ -67,6 +68,8 @@ impl MCPConnectionRepository for SqliteMCPConnectionRepository {
                     status: row.get(7)?,
                     tools_json: row.get(8)?,
                     error_message: row.get(9)?,
+                    tool_policies: row.get(12)?,
+                    auth_status: None,
                     created_at: row.get(10)?,
                     updated_at: row.get(11)?,
                 })
@@ -79,7 +82,7 @@ impl MCPConnectionRepository for SqliteMCPConnectionRepository {
     fn get_by_id(&self, id: &str) -> Result<Option<MCPServerConnection>, AppError> {
         let conn = crate::db::get_connection(&self.app)?;
         let result = conn.query_row(
-            "SELECT id, name, url, type, headers, env_vars, runtime_path, status, tools_json, error_message, created_at, updated_at FROM mcp_server_connections WHERE id = ?1",
+            "SELECT id, name, url, type, headers, env_vars, runtime_path, status, tools_json, error_message, created_at, updated_at, tool_policies FROM mcp_server_connections WHERE id = ?1",
             params![id],
             |row| {
                 Ok(MCPServerConnection {
@@ -93,6 +96,8 @@ impl MCPConnectionRepository for SqliteMCPConnectionRepository {
                     status: row.get(7)?,
                     tools_json: row.get(8)?,
                     error_message: row.get(9)?,
+                    tool_policies: row.get(12)?,
+                    auth_status: None,
                     created_at: row.get(10)?,
                     updated_at: row.get(11)?,
                 })
@@ -115,6 +120,7 @@ impl MCPConnectionRepository for SqliteMCPConnectionRepository {
         headers: Option<&str>,
         env_vars: Option<&str>,
         runtime_path: Option<&str>,
+        tool_policies: Option<&str>,
     ) -> Result<(), AppError> {
         let conn = crate::db::get_connection(&self.app)?;
         let now = std::time::SystemTime::now()
@@ -164,6 +170,13 @@ impl MCPConnectionRepository for SqliteMCPConnectionRepository {
             )?;
         }
 
+        if let Some(tool_policies) = tool_policies {
+            conn.execute(
+                "UPDATE mcp_server_connections SET tool_policies = ?1, updated_at = ?2 WHERE id = ?3",
+                params![tool_policies, now, id],
+            )?;
+        }
+
         Ok(())
     }
 
@@ -1,15 +1,35 @@
-use super::models::MCPServerConnection;
+use super::import::{detect_conflicts, export_mcp_config, parse_mcp_config, unique_name};
+use super::models::{
+    MCPImportConflict, MCPImportConflictStrategy, MCPServerConnection, MCPServerImportPreview,
+    MCPServerImportResult, MCPToolPolicies,
+};
+use super::oauth::{McpOAuthService, OAuthTokenStore};
 use super::repository::MCPConnectionRepository;
 use crate::error::AppError;
 use std::sync::Arc;
 
 pub struct MCPConnectionService {
     repository: Arc<dyn MCPConnectionRepository>,
+    oauth_store: Arc<OAuthTokenStore>,
 }
 
 impl MCPConnectionService {
-    pub fn new(repository: Arc<dyn MCPConnectionRepository>) -> Self {
-        Self { repository }
+    pub fn new(
+        repository: Arc<dyn MCPConnectionRepository>,
+        oauth_store: Arc<OAuthTokenStore>,
+    ) -> Self {
+        Self {
+            repository,
+            oauth_store,
+        }
+    }
+
+    fn with_auth_status(&self, mut connection: MCPServerConnection) -> MCPServerConnection {
+        if connection.r#type != "stdio" {
+            connection.auth_status = McpOAuthService::status(&self.oauth_store, &connection.url)
+                .map(|s| s.as_str().to_string());
+        }
+        connection
     }
 
     pub fn create(
@@ -21,7 +41,9 @@ impl MCPConnectionService {
         headers: String,
         env_vars: Option<String>,
         runtime_path: Option<String>,
+        tool_policies: Option<String>,
     ) -> Result<MCPServerConnection, AppError> {
+        Self::validate_tool_policies(tool_policies.as_deref())?;
         let now = std::time::SystemTime::now()
             .duration_since(std::time::UNIX_EPOCH)
             .unwrap()
@@ -38,6 +60,8 @@ impl MCPConnectionService {
             status: "disconnected".to_string(),
             tools_json: None,
             error_message: None,
+            tool_policies,
+            auth_status: None,
             created_at: now,
             updated_at: now,
         };
@@ -47,11 +71,19 @@ impl MCPConnectionService {
     }
 
     pub fn get_all(&self) -> Result<Vec<MCPServerConnection>, AppError> {
-        self.repository.get_all()
+        Ok(self
+            .repository
+            .get_all()?
+            .into_iter()
+            .map(|c| self.with_auth_status(c))
+            .collect())
     }
 
     pub fn get_by_id(&self, id: &str) -> Result<Option<MCPServerConnection>, AppError> {
-        self.repository.get_by_id(id)
+        Ok(self
+            .repository
+            .get_by_id(id)?
+            .map(|c| self.with_auth_status(c)))
     }
 
     pub fn update(
@@ -63,7 +95,9 @@ impl MCPConnectionService {
         headers: Option<String>,
         env_vars: Option<String>,
         runtime_path: Option<String>,
+        tool_policies: Option<String>,
     ) -> Result<(), AppError> {
+        Self::validate_tool_policies(tool_policies.as_deref())?;
         self.repository.update(
             &id,
             name.as_deref(),
@@ -72,9 +106,17 @@ impl MCPConnectionService {
             headers.as_deref(),
             env_vars.as_deref(),
             runtime_path.as_deref(),
+            tool_policies.as_deref(),
         )
     }
 
+    fn validate_tool_policies(tool_policies: Option<&str>) -> Result<(), AppError> {
+        MCPToolPolicies::parse(tool_policies)
+            .map_err(|e| AppError::Validation(format!("Invalid tool policies: {e}")))?
+            .validate()
+            .map_err(|e| AppError::Validation(format!("Invalid tool policies: {e}")))
+    }
+
     pub fn update_status(
         &self,
         id: String,
@@ -91,6 +133,139 @@ impl MCPConnectionService {
     }
 
     pub fn delete(&self, id: String) -> Result<(), AppError> {
+        if let Some(connection) = self.repository.get_by_id(&id)? {
+            if connection.r#type != "stdio" {
+                McpOAuthService::clear(&self.oauth_store, &connection.url).ok();
+            }
+        }
         self.repository.delete(&id)
     }
+
+    fn require(&self, id: &str) -> Result<MCPServerConnection, AppError> {
+        self.repository
+            .get_by_id(id)?
+            .ok_or_else(|| AppError::NotFound(format!("MCP connection not found: {id}")))
+    }
+
+    /// Run the OAuth authorization flow for a remote connection in the system browser.
+    pub async fn authorize(
+        &self,
+        app: &tauri::AppHandle,
+        id: &str,
+        client_id: Option<String>,
+    ) -> Result<MCPServerConnection, AppError> {
+        let connection = self.require(id)?;
+        if connection.r#type == "stdio" {
+            return Err(AppError::Validation(
+                "OAuth is only supported for remote (sse / http-streamable) connections"
+                    .to_string(),
+            ));
+        }
+        McpOAuthService::authorize(app, &connection.url, client_id).await?;
+        Ok(self.with_auth_status(connection))
+    }
+
+    /// Forget the OAuth tokens and client registration of a connection.
+    pub fn clear_authorization(&self, id: &str) -> Result<(), AppError> {
+        let connection = self.require(id)?;
+        McpOAuthService::clear(&self.oauth_store, &connection.url)
+    }
+
+    /// Parse an `mcpServers` config and report conflicts with existing connections
+    /// without writing anything.
+    pub fn preview_import(&self, content: &str) -> Result<MCPServerImportPreview, AppError> {
+        let (servers, warnings) = parse_mcp_config(content)?;
+        let existing = self.repository.get_all()?;
+        Ok(MCPServerImportPreview {
+            candidates: detect_conflicts(servers, &existing),
+            warnings,
+        })
+    }
+
+    /// Import servers from an `mcpServers` config.
+    ///
+    /// `names` restricts the import to the given server names (as shown in the
+    /// preview). Identical connections are always skipped; name conflicts are
+    /// resolved with `strategy`.
+    pub fn import(
+        &self,
+        content: &str,
+        names: Option<Vec<String>>,
+        strategy: MCPImportConflictStrategy,
+    ) -> Result<MCPServerImportResult, AppError> {
+        let preview = self.preview_import(content)?;
+        let mut taken: Vec<String> = self
+            .repository
+            .get_all()?
+            .into_iter()
+            .map(|c| c.name)
+            .collect();
+        let mut result = MCPServerImportResult::default();
+
+        for candidate in preview.candidates {
+            if names
+                .as_ref()
+                .is_some_and(|n| !n.iter().any(|name| name == &candidate.name))
+            {
+                continue;
+            }
+
+            let name = match (candidate.conflict, strategy) {
+                (MCPImportConflict::Duplicate, _)
+                | (MCPImportConflict::Name, MCPImportConflictStrategy::Skip) => {
+                    result.skipped.push(candidate.name);
+                    continue;
+                }
+                (MCPImportConflict::Name, MCPImportConflictStrategy::Overwrite) => {
+                    let Some(existing_id) = candidate.existing_id else {
+                        result.skipped.push(candidate.name);
+                        continue;
+                    };
+                    self.repository.update(
+                        &existing_id,
+                        None,
+                        Some(&candidate.url),
+                        Some(&candidate.r#type),
+                        Some(&candidate.headers),
+                        Some(candidate.env_vars.as_deref().unwrap_or("")),
+                        Some(candidate.runtime_path.as_deref().unwrap_or("")),
+                        None,
+                    )?;
+                    result.updated.push(existing_id);
+                    continue;
+                }
+                (MCPImportConflict::Name, MCPImportConflictStrategy::Rename) => {
+                    unique_name(&candidate.name, &taken)
+                }
+                (MCPImportConflict::None, _) => candidate.name,
+            };
+
+            taken.push(name.clone());
+            let connection = self.create(
+                uuid::Uuid::new_v4().to_string(),
+                name,
+                candidate.url,
+                candidate.r#type,
+                candidate.headers,
+                candidate.env_vars,
+                candidate.runtime_path,
+                None,
+            )?;
+            result.created.push(connection);
+        }
+
+        Ok(result)
+    }
+
+    /// Export connections in the Claude Desktop `mcpServers` format.
+    /// Exports every connection when `ids` is `None`.
+    pub fn export(&self, ids: Option<Vec<String>>) -> Result<String, AppError> {
+        let connections: Vec<MCPServerConnection> = self
+            .repository
+            .get_all()?
+            .into_iter()
+            .filter(|c| ids.as_ref().is_none_or(|ids| ids.contains(&c.id)))
+            .collect();
+        export_mcp_config(&connections)
+    }
 }
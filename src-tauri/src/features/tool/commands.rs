@@ -1,5 +1,5 @@
 use super::mcp::MCPClientService;
-use super::models::MCPTool;
+use super::models::{MCPTool, McpProcessStatus};
 use crate::error::AppError;
 use crate::features::tool::core::{ResolveMode, ToolRuntime};
 use crate::state::mcp_client_state::MCPClientState;
@@ -87,8 +87,9 @@ pub async fn call_mcp_tool(
     let args: serde_json::Value = serde_json::from_str(&arguments)
         .map_err(|e| AppError::Mcp(format!("Failed to parse arguments: {e}")))?;
 
-    MCPClientService::call_tool(
+    let result = MCPClientService::call_tool(
         &app,
+        &connection_id,
         url,
         r#type,
         headers,
@@ -98,7 +99,10 @@ pub async fn call_mcp_tool(
         runtime_path,
     )
     .await
-    .map_err(|e| AppError::Mcp(e.to_string()))
+    .map_err(|e| AppError::Mcp(e.to_string()))?;
+
+    serde_json::to_string(&result.content)
+        .map_err(|e| AppError::Generic(format!("Failed to serialize result: {e}")))
 }
 
 #[tauri::command]
@@ -108,6 +112,8 @@ pub async fn disconnect_mcp_client(
 ) -> Result<(), AppError> {
     let mut connection_info = state.connection_info.lock().await;
     connection_info.remove(&connection_id);
+    drop(connection_info);
+    state.supervisor.stop(&connection_id).await;
     Ok(())
 }
 
@@ -126,3 +132,48 @@ pub async fn get_active_tools_for_workspace(
 
     Ok(runtime.list_unified_info())
 }
+
+#[tauri::command]
+pub async fn get_mcp_server_processes(
+    state: State<'_, MCPClientState>,
+) -> Result<Vec<McpProcessStatus>, AppError> {
+    Ok(state.supervisor.statuses().await)
+}
+
+#[tauri::command]
+pub async fn get_mcp_server_process_status(
+    connection_id: String,
+    state: State<'_, MCPClientState>,
+) -> Result<Option<McpProcessStatus>, AppError> {
+    Ok(state.supervisor.status(&connection_id).await)
+}
+
+#[tauri::command]
+pub async fn get_mcp_server_logs(
+    connection_id: String,
+    lines: Option<usize>,
+    state: State<'_, MCPClientState>,
+) -> Result<Vec<String>, AppError> {
+    Ok(state
+        .supervisor
+        .logs(&connection_id, lines.unwrap_or(200))
+        .await)
+}
+
+#[tauri::command]
+pub async fn restart_mcp_server_process(
+    app: tauri::AppHandle,
+    connection_id: String,
+    state: State<'_, MCPClientState>,
+) -> Result<McpProcessStatus, AppError> {
+    state.supervisor.restart(&app, &connection_id).await
+}
+
+#[tauri::command]
+pub async fn stop_mcp_server_process(
+    connection_id: String,
+    state: State<'_, MCPClientState>,
+) -> Result<(), AppError> {
+    state.supervisor.stop(&connection_id).await;
+    Ok(())
+}
@@ -1,5 +1,113 @@
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
+/// A single piece of tool output, mirroring the MCP `ContentBlock` wire format.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ToolContentBlock {
+    Text {
+        text: String,
+    },
+    Image {
+        data: String,
+        #[serde(rename = "mimeType")]
+        mime_type: String,
+    },
+    Audio {
+        data: String,
+        #[serde(rename = "mimeType")]
+        mime_type: String,
+    },
+    Resource {
+        resource: ToolResourceContents,
+    },
+    ResourceLink {
+        uri: String,
+        #[serde(default)]
+        name: String,
+        #[serde(rename = "mimeType", default, skip_serializing_if = "Option::is_none")]
+        mime_type: Option<String>,
+    },
+}
+
+/// Contents of an embedded resource; exactly one of `text` or `blob` (base64) is set.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ToolResourceContents {
+    pub uri: String,
+    #[serde(rename = "mimeType", default, skip_serializing_if = "Option::is_none")]
+    pub mime_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blob: Option<String>,
+}
+
+impl ToolContentBlock {
+    /// Parse a JSON content array, keeping unknown block types as their raw JSON text.
+    pub fn parse_all(value: Value) -> Vec<Self> {
+        let items = match value {
+            Value::Array(items) => items,
+            other => vec![other],
+        };
+        items
+            .into_iter()
+            .map(|item| {
+                serde_json::from_value(item.clone()).unwrap_or_else(|_| Self::Text {
+                    text: item.to_string(),
+                })
+            })
+            .collect()
+    }
+
+    /// Binary payload (base64) and MIME type for blocks that carry one.
+    pub fn binary(&self) -> Option<(&str, &str)> {
+        match self {
+            Self::Image { data, mime_type } | Self::Audio { data, mime_type } => {
+                Some((data.as_str(), mime_type.as_str()))
+            }
+            Self::Resource { resource } => resource.blob.as_deref().map(|blob| {
+                (
+                    blob,
+                    resource
+                        .mime_type
+                        .as_deref()
+                        .unwrap_or("application/octet-stream"),
+                )
+            }),
+            Self::Text { .. } | Self::ResourceLink { .. } => None,
+        }
+    }
+
+    /// Text rendering of the block for models that only see the tool result string.
+    fn render(&self) -> String {
+        match self {
+            Self::Text { text } => text.clone(),
+            Self::Image { mime_type, .. } => format!("[image: {mime_type}]"),
+            Self::Audio { mime_type, .. } => format!("[audio: {mime_type}]"),
+            Self::Resource { resource } => resource.text.as_ref().map_or_else(
+                || {
+                    format!(
+                        "[resource: {} ({})]",
+                        resource.uri,
+                        resource
+                            .mime_type
+                            .as_deref()
+                            .unwrap_or("application/octet-stream")
+                    )
+                },
+                |text| format!("[resource: {}]\n{text}", resource.uri),
+            ),
+            Self::ResourceLink { uri, name, .. } => {
+                if name.is_empty() {
+                    format!("[resource link: {uri}]")
+                } else {
+                    format!("[resource link: {name} <{uri}>]")
+                }
+            }
+        }
+    }
+}
+
 /// Standardized result from any tool execution.
 #[derive(Debug, Clone)]
 pub struct ToolResult {
@@ -9,6 +117,8 @@ pub struct ToolResult {
     pub raw_size: usize,
     pub truncated: bool,
     pub metadata: Value,
+    /// Structured content blocks, when the tool returned more than plain text.
+    pub blocks: Vec<ToolContentBlock>,
 }
 
 impl ToolResult {
@@ -22,6 +132,7 @@ impl ToolResult {
             raw_size: len,
             truncated: false,
             metadata: Value::Null,
+            blocks: Vec::new(),
         }
     }
 
@@ -35,9 +146,55 @@ impl ToolResult {
             raw_size: len,
             truncated: false,
             metadata: Value::Null,
+            blocks: Vec::new(),
         }
     }
 
+    /// Build a result from content blocks; `content` holds their text rendering.
+    pub fn from_blocks(
+        tool_name: impl Into<String>,
+        blocks: Vec<ToolContentBlock>,
+        is_error: bool,
+    ) -> Self {
+        let content = blocks
+            .iter()
+            .map(ToolContentBlock::render)
+            .collect::<Vec<_>>()
+            .join("\n");
+        let mut result = if is_error {
+            Self::err(tool_name, content)
+        } else {
+            Self::ok(tool_name, content)
+        };
+        result.blocks = blocks;
+        result
+    }
+
+    /// Images returned by the tool as `(mime_type, base64 data)` pairs.
+    pub fn images(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.blocks.iter().filter_map(|block| match block {
+            ToolContentBlock::Image { data, mime_type } => {
+                Some((mime_type.as_str(), data.as_str()))
+            }
+            _ => None,
+        })
+    }
+
+    /// Truncate the content to at most `max_chars` characters, keeping `raw_size`
+    /// so callers can report how much was dropped.
+    #[must_use]
+    pub fn truncate_to(mut self, max_chars: usize) -> Self {
+        let total_chars = self.content.chars().count();
+        if total_chars <= max_chars {
+            return self;
+        }
+        let kept: String = self.content.chars().take(max_chars).collect();
+        self.content =
+            format!("{kept}\n[... truncated: showing {max_chars} of {total_chars} characters]");
+        self.truncated = true;
+        self
+    }
+
     pub fn to_llm_content(&self) -> String {
         if self.is_error {
             serde_json::json!({ "error": self.content }).to_string()
@@ -46,3 +203,48 @@ impl ToolResult {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn truncate_to_keeps_raw_size() {
+        let result = ToolResult::ok("t", "é".repeat(20)).truncate_to(5);
+        assert!(result.truncated);
+        assert_eq!(result.raw_size, "é".repeat(20).len());
+        assert!(result.content.starts_with("ééééé\n[... truncated"));
+
+        let short = ToolResult::ok("t", "abc").truncate_to(5);
+        assert!(!short.truncated);
+        assert_eq!(short.content, "abc");
+    }
+
+    #[test]
+    fn from_blocks_renders_rich_content() {
+        let blocks = ToolContentBlock::parse_all(serde_json::json!([
+            { "type": "text", "text": "Screenshot taken" },
+            { "type": "image", "data": "aGk=", "mimeType": "image/png" },
+            { "type": "resource", "resource": { "uri": "file:///a.txt", "text": "hello" } },
+            { "type": "resource_link", "uri": "file:///b.pdf", "name": "b.pdf" },
+            { "type": "mystery", "x": 1 }
+        ]));
+        assert_eq!(blocks.len(), 5);
+        assert_eq!(blocks[1].binary(), Some(("aGk=", "image/png")));
+
+        let result = ToolResult::from_blocks("shot", blocks, true);
+        assert!(result.is_error);
+        assert_eq!(
+            result.images().collect::<Vec<_>>(),
+            vec![("image/png", "aGk=")]
+        );
+        assert!(result
+            .content
+            .starts_with("Screenshot taken\n[image: image/png]\n"));
+        assert!(result.content.contains("[resource: file:///a.txt]\nhello"));
+        assert!(result
+            .content
+            .contains("[resource link: b.pdf <file:///b.pdf>]"));
+        assert!(result.content.contains("\"mystery\""));
+    }
+}
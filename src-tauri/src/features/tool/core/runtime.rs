@@ -2,7 +2,7 @@ use super::context::ToolExecutionContext;
 use super::deps::ToolDeps;
 use super::llm_adapter::tool_specs_to_llm_tools;
 use super::result::ToolResult;
-use super::spec::{ToolInteraction, ToolSpec};
+use super::spec::{ToolBehavior, ToolInteraction, ToolSpec};
 use super::traits::ToolSource;
 use crate::error::AppError;
 use crate::features::tool::builtin::BuiltinToolSource;
@@ -109,6 +109,7 @@ impl ToolRuntime {
                             deps.app.clone(),
                             connection,
                             selected,
+                            deps.artifact_service.clone(),
                         )));
                     }
                 }
@@ -137,6 +138,15 @@ impl ToolRuntime {
         self.specs.iter().find(|s| s.name == name)
     }
 
+    /// Names of tools that always require user permission before running.
+    pub fn sensitive_tool_names(&self) -> HashSet<String> {
+        self.specs
+            .iter()
+            .filter(|s| s.behavior.sensitive)
+            .map(|s| s.name.clone())
+            .collect()
+    }
+
     pub async fn execute(
         &self,
         tool_name: &str,
@@ -152,39 +162,46 @@ impl ToolRuntime {
             .find_spec(tool_name)
             .ok_or_else(|| AppError::Validation(format!("Tool spec not found: {tool_name}")))?;
 
-        let timeout = spec.behavior.default_timeout;
+        let behavior = spec.behavior.clone();
         let source_id = spec.source_id.clone();
-
         let source = self.sources[*source_idx].clone();
-        let tool_name_owned = tool_name.to_string();
-        let ctx = ctx.clone();
-
-        let exec_future = async move { source.execute(&tool_name_owned, arguments, &ctx).await };
 
         let start = std::time::Instant::now();
-        let execution_result = match timeout {
-            None if spec.behavior.interaction == ToolInteraction::AwaitUser => {
-                tokio::select! {
-                    result = exec_future => result,
-                    _ = cancellation_rx.recv() => Err(AppError::Cancelled),
-                }
-            }
-            None => exec_future.await,
-            Some(duration) => {
-                tokio::select! {
-                    result = tokio::time::timeout(duration, exec_future) => {
-                        match result {
-                            Ok(r) => r,
-                            Err(_) => Err(AppError::Generic(
-                                format!("Tool execution timed out after {} seconds", duration.as_secs()),
-                            )),
-                        }
-                    }
-                    _ = cancellation_rx.recv() => Err(AppError::Cancelled),
-                }
+        let mut attempt = 0;
+        let execution_result = loop {
+            let result = Self::execute_once(
+                &source,
+                tool_name,
+                arguments.clone(),
+                ctx,
+                &behavior,
+                cancellation_rx,
+            )
+            .await;
+
+            let retry = match (&result, behavior.retry) {
+                (Err(e), Some(retry)) if e.is_transient() && attempt < retry.max_retries => retry,
+                _ => break result,
+            };
+            let delay = retry.delay_for(attempt);
+            attempt += 1;
+            tracing::warn!(
+                tool = %tool_name,
+                attempt,
+                delay_ms = delay.as_millis() as u64,
+                "Retrying tool after transient failure"
+            );
+            tokio::select! {
+                () = tokio::time::sleep(delay) => {}
+                _ = cancellation_rx.recv() => break Err(AppError::Cancelled),
             }
         };
 
+        let execution_result = match behavior.max_output_chars {
+            Some(max_chars) => execution_result.map(|r| r.truncate_to(max_chars)),
+            None => execution_result,
+        };
+
         let duration_ms = start.elapsed().as_millis() as u64;
         let sentry_result: Result<(), Box<dyn std::error::Error>> = execution_result
             .as_ref()
@@ -203,6 +220,40 @@ impl ToolRuntime {
 
         execution_result
     }
+
+    async fn execute_once(
+        source: &Arc<dyn ToolSource>,
+        tool_name: &str,
+        arguments: Value,
+        ctx: &ToolExecutionContext,
+        behavior: &ToolBehavior,
+        cancellation_rx: &mut broadcast::Receiver<()>,
+    ) -> Result<ToolResult, AppError> {
+        let exec_future = source.execute(tool_name, arguments, ctx);
+
+        match behavior.default_timeout {
+            None if behavior.interaction == ToolInteraction::AwaitUser => {
+                tokio::select! {
+                    result = exec_future => result,
+                    _ = cancellation_rx.recv() => Err(AppError::Cancelled),
+                }
+            }
+            None => exec_future.await,
+            Some(duration) => {
+                tokio::select! {
+                    result = tokio::time::timeout(duration, exec_future) => {
+                        match result {
+                            Ok(r) => r,
+                            Err(_) => Err(AppError::Generic(
+                                format!("Tool execution timed out after {} seconds", duration.as_secs()),
+                            )),
+                        }
+                    }
+                    _ = cancellation_rx.recv() => Err(AppError::Cancelled),
+                }
+            }
+        }
+    }
 }
 
 pub fn parse_tool_arguments(tool_name: &str, arguments_str: &str) -> Result<Value, AppError> {
@@ -262,7 +313,8 @@ fn escape_raw_newlines_in_json_strings(input: &str) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::features::tool::core::spec::{ToolBehavior, ToolSpec};
+    use crate::features::tool::core::spec::RetryPolicy;
+    use std::time::Duration;
     use crate::features::tool::core::traits::ToolSource;
     use async_trait::async_trait;
 
@@ -314,6 +366,17 @@ mod tests {
         assert_eq!(runtime.list_llm_tools().len(), 1);
     }
 
+    #[test]
+    fn retry_backoff_is_exponential_and_capped() {
+        let retry = RetryPolicy {
+            max_retries: 10,
+            backoff: Duration::from_millis(500),
+        };
+        assert_eq!(retry.delay_for(0), Duration::from_millis(500));
+        assert_eq!(retry.delay_for(2), Duration::from_secs(2));
+        assert_eq!(retry.delay_for(10), Duration::from_secs(30));
+    }
+
     #[test]
     fn parse_tool_arguments_repairs_newlines() {
         let args = "{\n  \"path\": \"/tmp/test\nfile\"\n}";
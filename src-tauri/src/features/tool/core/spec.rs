@@ -7,11 +7,32 @@ pub enum ToolInteraction {
     AwaitUser,
 }
 
+/// Retry policy for transient failures (see `AppError::is_transient`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    pub max_retries: u32,
+    pub backoff: Duration,
+}
+
+impl RetryPolicy {
+    const MAX_DELAY: Duration = Duration::from_secs(30);
+
+    /// Exponential backoff: `backoff * 2^attempt`, capped at 30 seconds.
+    pub fn delay_for(&self, attempt: u32) -> Duration {
+        self.backoff
+            .saturating_mul(2u32.saturating_pow(attempt))
+            .min(Self::MAX_DELAY)
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct ToolBehavior {
     pub interaction: ToolInteraction,
     pub default_timeout: Option<Duration>,
     pub sensitive: bool,
+    pub retry: Option<RetryPolicy>,
+    /// Results longer than this many characters are truncated before reaching the model.
+    pub max_output_chars: Option<usize>,
 }
 
 impl ToolBehavior {
@@ -20,6 +41,8 @@ impl ToolBehavior {
             interaction: ToolInteraction::Immediate,
             default_timeout: Some(Duration::from_secs(60)),
             sensitive: false,
+            retry: None,
+            max_output_chars: None,
         }
     }
 
@@ -28,6 +51,8 @@ impl ToolBehavior {
             interaction: ToolInteraction::AwaitUser,
             default_timeout: None,
             sensitive: false,
+            retry: None,
+            max_output_chars: None,
         }
     }
 }
@@ -1,13 +1,16 @@
 use crate::error::AppError;
+use crate::features::mcp_connection::oauth::McpOAuthService;
 use crate::features::sandbox::{RuntimeKind, SandboxService};
 #[cfg(windows)]
 use crate::path_util;
 use crate::features::tool::models::MCPTool;
+use crate::state::MCPClientState;
 use rust_mcp_sdk::{
+    error::McpSdkError,
     mcp_client::{client_runtime, ClientHandler, ClientRuntime},
     schema::{
-        CallToolRequestParams, ClientCapabilities, Implementation, InitializeRequestParams,
-        LATEST_PROTOCOL_VERSION,
+        CallToolRequestParams, CallToolResult, ClientCapabilities, Implementation,
+        InitializeRequestParams, LATEST_PROTOCOL_VERSION,
     },
     McpClient,
 };
@@ -21,6 +24,9 @@ use std::sync::Arc;
 use std::path::PathBuf;
 use tauri::{AppHandle, Manager};
 
+/// Prefix of the stderr line in which a supervised stdio server reports its pid.
+pub const PID_MARKER: &str = "__cogito_mcp_pid__ ";
+
 // Simple client handler - we don't need to handle any server messages for this use case
 struct SimpleClientHandler;
 
@@ -52,6 +58,36 @@ impl MCPClientService {
         None
     }
 
+    /// Add a Bearer token from the OAuth store (refreshed if needed) unless
+    /// the user configured an explicit `Authorization` header.
+    async fn with_oauth_header(
+        app: &AppHandle,
+        url: &str,
+        headers: Option<HashMap<String, String>>,
+    ) -> Result<Option<HashMap<String, String>>, AppError> {
+        let has_explicit = headers
+            .as_ref()
+            .is_some_and(|h| h.keys().any(|k| k.eq_ignore_ascii_case("authorization")));
+        if has_explicit {
+            return Ok(headers);
+        }
+        let Some(bearer) = McpOAuthService::authorization_header(app, url).await? else {
+            return Ok(headers);
+        };
+        let mut headers = headers.unwrap_or_default();
+        headers.insert("Authorization".to_string(), bearer);
+        Ok(Some(headers))
+    }
+
+    /// Transport and I/O failures are transient (worth retrying); protocol and
+    /// tool errors reported by the server are not.
+    fn classify_error(error: &McpSdkError, message: String) -> AppError {
+        match error {
+            McpSdkError::Transport(_) | McpSdkError::Io(_) => AppError::McpTransport(message),
+            _ => AppError::Generic(message),
+        }
+    }
+
     /// Create client details for MCP initialization
     fn create_client_details() -> InitializeRequestParams {
         InitializeRequestParams {
@@ -73,6 +109,53 @@ impl MCPClientService {
         headers: Option<String>,
         env_vars_json: Option<String>,
         runtime_path: Option<String>,
+    ) -> Result<Arc<ClientRuntime>, AppError> {
+        Self::start_client(
+            app,
+            url,
+            r#type,
+            headers,
+            env_vars_json,
+            runtime_path,
+            SimpleClientHandler,
+            false,
+        )
+        .await
+    }
+
+    /// Start a long-lived stdio client whose stderr is delivered to `handler`.
+    /// On Unix the server is launched through a shell shim that reports its pid
+    /// on stderr (see [`PID_MARKER`]).
+    pub async fn start_supervised_stdio_client(
+        app: &AppHandle,
+        url: String,
+        env_vars_json: Option<String>,
+        runtime_path: Option<String>,
+        handler: impl ClientHandler,
+    ) -> Result<Arc<ClientRuntime>, AppError> {
+        Self::start_client(
+            app,
+            url,
+            "stdio".to_string(),
+            None,
+            env_vars_json,
+            runtime_path,
+            handler,
+            true,
+        )
+        .await
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn start_client(
+        app: &AppHandle,
+        url: String,
+        r#type: String,
+        headers: Option<String>,
+        env_vars_json: Option<String>,
+        runtime_path: Option<String>,
+        handler: impl ClientHandler,
+        report_pid: bool,
     ) -> Result<Arc<ClientRuntime>, AppError> {
         SandboxService::ensure_ready(app).await?;
 
@@ -87,7 +170,10 @@ impl MCPClientService {
             )));
         }
 
-        let custom_headers = Self::parse_json_map(&headers);
+        let mut custom_headers = Self::parse_json_map(&headers);
+        if r#type != "stdio" {
+            custom_headers = Self::with_oauth_header(app, &url, custom_headers).await?;
+        }
         let mut env_vars = Self::parse_json_map(&env_vars_json);
 
         // Fallback: If it's stdio and headers are provided but env_vars are not, use headers as env_vars (legacy support)
@@ -96,7 +182,6 @@ impl MCPClientService {
         }
 
         let client_details = Self::create_client_details();
-        let handler = SimpleClientHandler {};
 
         let client = if r#type == "sse" {
             // Create SSE transport with custom headers if provided
@@ -174,6 +259,12 @@ impl MCPClientService {
                 }
             }
 
+            let (command, args) = if report_pid {
+                Self::with_pid_marker(command, args)
+            } else {
+                (command, args)
+            };
+
             // Use environment variables
             let transport = match StdioTransport::create_with_server_launch(
                 &command,
@@ -198,7 +289,7 @@ impl MCPClientService {
         if let Err(e) = client.clone().start().await {
             let err_msg = format!("Failed to start MCP client for {url}: {e}");
             tracing::error!("{}", err_msg);
-            return Err(AppError::Generic(err_msg));
+            return Err(Self::classify_error(&e, err_msg));
         }
 
         Ok(client)
@@ -261,9 +352,14 @@ impl MCPClientService {
         Ok(tools)
     }
 
-    /// Call a tool using MCP client
+    /// Call a tool using MCP client.
+    ///
+    /// Stdio servers run under the process supervisor and stay alive between calls;
+    /// remote servers get a fresh client per call.
+    #[allow(clippy::too_many_arguments)]
     pub async fn call_tool(
         app: &AppHandle,
+        connection_id: &str,
         url: String,
         r#type: String,
         headers: Option<String>,
@@ -271,7 +367,41 @@ impl MCPClientService {
         tool_name: String,
         arguments: serde_json::Value,
         runtime_path: Option<String>,
-    ) -> Result<String, AppError> {
+    ) -> Result<CallToolResult, AppError> {
+        // Convert arguments from Value to Map if it's an object
+        let arguments_map = match arguments {
+            serde_json::Value::Object(map) => Some(map),
+            _ => None,
+        };
+        let params = CallToolRequestParams {
+            name: tool_name.clone(),
+            arguments: arguments_map,
+        };
+
+        if r#type == "stdio" {
+            if let Some(state) = app.try_state::<MCPClientState>() {
+                let supervisor = state.supervisor.clone();
+                let (client, generation) = supervisor
+                    .client_for(app, connection_id, &url, env_vars_json, runtime_path)
+                    .await?;
+                return match client.call_tool(params).await {
+                    Ok(result) => Ok(result),
+                    Err(e) => {
+                        let err_msg =
+                            format!("Failed to call tool {tool_name} on MCP server {url}: {e}");
+                        tracing::error!("{}", err_msg);
+                        let error = Self::classify_error(&e, err_msg);
+                        if error.is_transient() {
+                            supervisor
+                                .report_failure(app, connection_id, generation, &error.to_string())
+                                .await;
+                        }
+                        Err(error)
+                    }
+                };
+            }
+        }
+
         let client = Self::create_and_start_client(
             app,
             url.clone(),
@@ -282,16 +412,6 @@ impl MCPClientService {
         )
         .await?;
 
-        // Call the tool
-        // Convert arguments from Value to Map if it's an object
-        let arguments_map = match arguments {
-            serde_json::Value::Object(map) => Some(map),
-            _ => None,
-        };
-        let params = CallToolRequestParams {
-            name: tool_name.clone(),
-            arguments: arguments_map,
-        };
         let result = match client.call_tool(params).await {
             Ok(r) => r,
             Err(e) => {
@@ -302,18 +422,35 @@ impl MCPClientService {
                     e
                 );
                 tracing::error!("{}", err_msg);
-                return Err(AppError::Generic(err_msg));
+                let _ = client.shut_down().await;
+                return Err(Self::classify_error(&e, err_msg));
             }
         };
 
-        // Serialize result to JSON string
-        let result_json = serde_json::to_string(&result.content)
-            .map_err(|e| AppError::Generic(format!("Failed to serialize result: {e}")))?;
-
         // Clean up - shut down the client connection
         let _ = client.shut_down().await;
 
-        Ok(result_json)
+        Ok(result)
+    }
+
+    /// Launch `command` through `sh` so the server reports its pid before `exec`
+    /// replaces the shell (keeping the same pid).
+    #[cfg(unix)]
+    fn with_pid_marker(command: String, args: Vec<String>) -> (String, Vec<String>) {
+        let mut wrapped = vec![
+            "-c".to_string(),
+            format!("echo \"{PID_MARKER}$$\" >&2; exec \"$@\""),
+            "mcp-server".to_string(),
+            command,
+        ];
+        wrapped.extend(args);
+        ("/bin/sh".to_string(), wrapped)
+    }
+
+    /// Servers run under `cmd.exe /c` on Windows, so no pid is reported.
+    #[cfg(not(unix))]
+    fn with_pid_marker(command: String, args: Vec<String>) -> (String, Vec<String>) {
+        (command, args)
     }
 
     fn runtime_kind_for_command(command: &str) -> Option<RuntimeKind> {
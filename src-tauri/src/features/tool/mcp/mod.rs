@@ -1,7 +1,10 @@
 pub mod client;
+pub mod process_log;
 pub mod refresh;
 pub mod source;
+pub mod supervisor;
 
 pub use client::MCPClientService;
 pub use refresh::MCPToolRefreshService;
 pub use source::McpConnectionSource;
+pub use supervisor::McpProcessSupervisor;
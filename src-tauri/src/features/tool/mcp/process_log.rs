@@ -0,0 +1,106 @@
+use std::collections::VecDeque;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+/// Lines kept in memory for `tail` queries.
+const MAX_MEMORY_LINES: usize = 500;
+/// The log file is rotated to `<name>.1` once it grows past this size.
+const MAX_FILE_BYTES: u64 = 512 * 1024;
+
+/// Rolling stderr log for one MCP server process.
+///
+/// Lines are kept in a bounded in-memory buffer and appended to a file that is
+/// rotated once it grows too large, so logs survive app restarts.
+pub struct ProcessLog {
+    path: Option<PathBuf>,
+    lines: Mutex<VecDeque<String>>,
+}
+
+impl ProcessLog {
+    /// Open the log at `path`, seeding the buffer with the tail of the existing file.
+    pub fn open(path: Option<PathBuf>) -> Self {
+        let mut lines = VecDeque::new();
+        if let Some(existing) = path.as_ref().and_then(|p| fs::read_to_string(p).ok()) {
+            for line in existing.lines() {
+                if lines.len() == MAX_MEMORY_LINES {
+                    lines.pop_front();
+                }
+                lines.push_back(line.to_string());
+            }
+        }
+        Self {
+            path,
+            lines: Mutex::new(lines),
+        }
+    }
+
+    pub fn append(&self, line: &str) {
+        let stamped = format!(
+            "{} {line}",
+            chrono::Utc::now().format("%Y-%m-%dT%H:%M:%S%.3fZ")
+        );
+
+        if let Ok(mut lines) = self.lines.lock() {
+            if lines.len() == MAX_MEMORY_LINES {
+                lines.pop_front();
+            }
+            lines.push_back(stamped.clone());
+        }
+
+        if let Some(path) = &self.path {
+            if let Err(e) = Self::write_line(path, &stamped) {
+                tracing::debug!("Failed to write MCP process log {}: {e}", path.display());
+            }
+        }
+    }
+
+    /// The last `count` lines, oldest first.
+    pub fn tail(&self, count: usize) -> Vec<String> {
+        self.lines.lock().map_or_else(
+            |_| Vec::new(),
+            |lines| {
+                let skip = lines.len().saturating_sub(count);
+                lines.iter().skip(skip).cloned().collect()
+            },
+        )
+    }
+
+    fn write_line(path: &PathBuf, line: &str) -> std::io::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        if fs::metadata(path).is_ok_and(|m| m.len() > MAX_FILE_BYTES) {
+            fs::rename(path, path.with_extension("log.1"))?;
+        }
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        writeln!(file, "{line}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_tail_and_rotates_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("server.log");
+        let log = ProcessLog::open(Some(path.clone()));
+
+        let filler = "x".repeat(1024);
+        for i in 0..=MAX_MEMORY_LINES {
+            log.append(&format!("{i} {filler}"));
+        }
+
+        let tail = log.tail(2);
+        assert_eq!(tail.len(), 2);
+        assert!(tail[1].contains(&format!(" {MAX_MEMORY_LINES} ")));
+        assert_eq!(log.tail(usize::MAX).len(), MAX_MEMORY_LINES);
+        assert!(path.with_extension("log.1").exists());
+
+        let reopened = ProcessLog::open(Some(path));
+        assert_eq!(reopened.tail(1), tail[1..].to_vec());
+    }
+}
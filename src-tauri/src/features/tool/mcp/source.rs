@@ -1,33 +1,170 @@
 use crate::error::AppError;
-use crate::features::mcp_connection::models::MCPServerConnection;
+use crate::features::artifacts::{Artifact, ArtifactService};
+use crate::features::mcp_connection::models::{
+    MCPServerConnection, MCPToolPolicies, MCPToolPolicy,
+};
 use crate::features::tool::core::context::ToolExecutionContext;
-use crate::features::tool::core::result::ToolResult;
-use crate::features::tool::core::spec::{ToolBehavior, ToolSpec};
+use crate::features::tool::core::result::{ToolContentBlock, ToolResult};
+use crate::features::tool::core::spec::{RetryPolicy, ToolBehavior, ToolSpec};
 use crate::features::tool::core::traits::ToolSource;
 use crate::features::tool::mcp::client::MCPClientService;
 use crate::features::tool::models::MCPTool;
 use async_trait::async_trait;
+use base64::{engine::general_purpose, Engine as _};
 use serde_json::Value;
 use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Duration;
 use tauri::AppHandle;
 
+const DEFAULT_RETRY_BACKOFF_MS: u64 = 500;
+
 pub struct McpConnectionSource {
     app: AppHandle,
     connection: MCPServerConnection,
     selected_tool_names: HashSet<String>,
+    policies: MCPToolPolicies,
+    artifact_service: Arc<ArtifactService>,
 }
 
 impl McpConnectionSource {
-    pub const fn new(
+    pub fn new(
         app: AppHandle,
         connection: MCPServerConnection,
         selected_tool_names: HashSet<String>,
+        artifact_service: Arc<ArtifactService>,
     ) -> Self {
+        let policies =
+            MCPToolPolicies::parse(connection.tool_policies.as_deref()).unwrap_or_else(|e| {
+                tracing::warn!(
+                    "Ignoring invalid tool policies for MCP connection {}: {e}",
+                    connection.id
+                );
+                MCPToolPolicies::default()
+            });
         Self {
             app,
             connection,
             selected_tool_names,
+            policies,
+            artifact_service,
+        }
+    }
+
+    /// Artifact filename for a binary block: the resource's own name when it has a usable
+    /// one, otherwise `<tool>-<n>.<ext>`.
+    fn binary_filename(tool_name: &str, index: usize, block: &ToolContentBlock) -> String {
+        if let ToolContentBlock::Resource { resource } = block {
+            let name = resource
+                .uri
+                .rsplit('/')
+                .next()
+                .unwrap_or_default()
+                .split(['?', '#'])
+                .next()
+                .unwrap_or_default();
+            let usable = name.contains('.')
+                && !name.starts_with('.')
+                && name
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_'));
+            if usable {
+                return name.to_string();
+            }
+        }
+
+        let stem: String = tool_name
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '-' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        let ext = block
+            .binary()
+            .map_or("bin", |(_, mime)| Self::extension_for_mime(mime));
+        format!("{stem}-{}.{ext}", index + 1)
+    }
+
+    fn extension_for_mime(mime: &str) -> &'static str {
+        match mime {
+            "image/png" => "png",
+            "image/jpeg" | "image/jpg" => "jpg",
+            "image/gif" => "gif",
+            "image/webp" => "webp",
+            "image/svg+xml" => "svg",
+            "audio/wav" | "audio/x-wav" => "wav",
+            "audio/mpeg" | "audio/mp3" => "mp3",
+            "audio/ogg" => "ogg",
+            "audio/webm" => "webm",
+            "application/pdf" => "pdf",
+            "application/json" => "json",
+            "application/zip" => "zip",
+            "text/plain" => "txt",
+            "text/csv" => "csv",
+            _ => "bin",
+        }
+    }
+
+    /// Save image, audio and blob resource blocks as chat artifacts. Failures are logged
+    /// and skipped so the text part of the result still reaches the model.
+    async fn save_binary_outputs(
+        &self,
+        tool_name: &str,
+        blocks: &[ToolContentBlock],
+        ctx: &ToolExecutionContext,
+    ) -> Vec<Artifact> {
+        let mut saved = Vec::new();
+        for (index, block) in blocks.iter().enumerate() {
+            let Some((data, mime_type)) = block.binary() else {
+                continue;
+            };
+            let bytes = match general_purpose::STANDARD.decode(data.trim()) {
+                Ok(bytes) => bytes,
+                Err(e) => {
+                    tracing::warn!("Skipping undecodable {mime_type} output from {tool_name}: {e}");
+                    continue;
+                }
+            };
+            let filename = Self::binary_filename(tool_name, index, block);
+            match self
+                .artifact_service
+                .create_binary(
+                    ctx,
+                    &format!("{tool_name} output"),
+                    &filename,
+                    &bytes,
+                    mime_type,
+                )
+                .await
+            {
+                Ok(artifact) => saved.push(artifact),
+                Err(e) => tracing::warn!("Failed to save {mime_type} output from {tool_name}: {e}"),
+            }
         }
+        saved
+    }
+
+    fn behavior_for(policy: &MCPToolPolicy) -> ToolBehavior {
+        let mut behavior = ToolBehavior::immediate();
+        if let Some(secs) = policy.timeout_secs {
+            behavior.default_timeout = Some(Duration::from_secs(secs));
+        }
+        behavior.retry = policy
+            .max_retries
+            .filter(|n| *n > 0)
+            .map(|max_retries| RetryPolicy {
+                max_retries,
+                backoff: Duration::from_millis(
+                    policy.retry_backoff_ms.unwrap_or(DEFAULT_RETRY_BACKOFF_MS),
+                ),
+            });
+        behavior.max_output_chars = policy.max_result_chars;
+        behavior.sensitive = policy.sensitive.unwrap_or(false);
+        behavior
     }
 
     fn cached_tools(&self) -> Vec<MCPTool> {
@@ -47,13 +184,14 @@ impl McpConnectionSource {
                     .input_schema
                     .as_ref()
                     .and_then(|s| serde_json::from_str(s).ok());
+                let behavior = Self::behavior_for(&self.policies.for_tool(&t.name));
                 ToolSpec::new(
                     t.name,
                     t.description,
                     parameters,
                     self.connection.id.clone(),
                     self.connection.name.clone(),
-                    ToolBehavior::immediate(),
+                    behavior,
                 )
             })
             .collect()
@@ -78,7 +216,7 @@ impl ToolSource for McpConnectionSource {
         &self,
         tool_name: &str,
         arguments: Value,
-        _ctx: &ToolExecutionContext,
+        ctx: &ToolExecutionContext,
     ) -> Result<ToolResult, AppError> {
         if !self.selected_tool_names.contains(tool_name) {
             return Err(AppError::Validation(format!(
@@ -93,8 +231,9 @@ impl ToolSource for McpConnectionSource {
             Some(self.connection.headers.clone())
         };
 
-        let result_json = MCPClientService::call_tool(
+        let call_result = MCPClientService::call_tool(
             &self.app,
+            &self.connection.id,
             self.connection.url.clone(),
             self.connection.r#type.clone(),
             headers,
@@ -104,8 +243,79 @@ impl ToolSource for McpConnectionSource {
             self.connection.runtime_path.clone(),
         )
         .await
-        .map_err(|e| AppError::Generic(format!("Failed to execute tool {tool_name}: {e}")))?;
+        .map_err(|e| match e {
+            AppError::McpTransport(msg) => {
+                AppError::McpTransport(format!("Failed to execute tool {tool_name}: {msg}"))
+            }
+            e => AppError::Generic(format!("Failed to execute tool {tool_name}: {e}")),
+        })?;
+
+        let mut blocks = ToolContentBlock::parse_all(serde_json::to_value(&call_result.content)?);
+        if blocks.is_empty() {
+            if let Some(structured) = call_result.structured_content {
+                blocks.push(ToolContentBlock::Text {
+                    text: Value::Object(structured).to_string(),
+                });
+            }
+        }
+
+        let mut result =
+            ToolResult::from_blocks(tool_name, blocks, call_result.is_error.unwrap_or(false));
+
+        let saved = self
+            .save_binary_outputs(tool_name, &result.blocks, ctx)
+            .await;
+        if !saved.is_empty() {
+            let names: Vec<&str> = saved.iter().map(|a| a.filename.as_str()).collect();
+            result.content = format!(
+                "{}\n[saved as artifacts: {}]",
+                result.content,
+                names.join(", ")
+            );
+            result.metadata = serde_json::json!({
+                "artifactIds": saved.iter().map(|a| a.id.as_str()).collect::<Vec<_>>(),
+            });
+        }
 
-        Ok(ToolResult::ok(tool_name, result_json))
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::features::tool::core::result::ToolResourceContents;
+
+    #[test]
+    fn binary_filename_prefers_resource_name() {
+        let image = ToolContentBlock::Image {
+            data: String::new(),
+            mime_type: "image/png".to_string(),
+        };
+        assert_eq!(
+            McpConnectionSource::binary_filename("browser.screenshot", 0, &image),
+            "browser_screenshot-1.png"
+        );
+
+        let resource = |uri: &str| ToolContentBlock::Resource {
+            resource: ToolResourceContents {
+                uri: uri.to_string(),
+                mime_type: Some("application/pdf".to_string()),
+                text: None,
+                blob: Some(String::new()),
+            },
+        };
+        assert_eq!(
+            McpConnectionSource::binary_filename(
+                "fetch",
+                2,
+                &resource("file:///tmp/report.pdf?v=1")
+            ),
+            "report.pdf"
+        );
+        assert_eq!(
+            McpConnectionSource::binary_filename("fetch", 2, &resource("db://rows/42")),
+            "fetch-3.pdf"
+        );
     }
 }
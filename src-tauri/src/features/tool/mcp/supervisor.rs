@@ -0,0 +1,692 @@
+//! Supervision for stdio MCP servers.
+//!
+//! Stdio servers are kept alive between tool calls. The supervisor captures their
+//! stderr into a rolling per-connection log, health-checks them with `ping`, restarts
+//! crashed servers with exponential backoff and records their pids so servers orphaned
+//! by an app crash can be killed on the next launch.
+
+use super::client::{MCPClientService, PID_MARKER};
+use super::process_log::ProcessLog;
+use crate::error::AppError;
+use crate::features::tool::models::{McpProcessState, McpProcessStatus};
+use rust_mcp_sdk::mcp_client::{ClientHandler, ClientRuntime};
+use rust_mcp_sdk::schema::RpcError;
+use rust_mcp_sdk::McpClient;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::future::Future;
+use std::path::PathBuf;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tauri::AppHandle;
+use tokio::sync::Mutex;
+
+const HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+const PING_TIMEOUT: Duration = Duration::from_secs(10);
+const MAX_CONSECUTIVE_RESTARTS: u32 = 5;
+const RESTART_BASE_DELAY: Duration = Duration::from_secs(1);
+const RESTART_MAX_DELAY: Duration = Duration::from_secs(60);
+/// A server that stayed up this long has its crash counter reset.
+const STABLE_UPTIME: Duration = Duration::from_secs(120);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct LaunchSpec {
+    url: String,
+    env_vars_json: Option<String>,
+    runtime_path: Option<String>,
+}
+
+struct SupervisedServer {
+    spec: LaunchSpec,
+    state: McpProcessState,
+    client: Option<Arc<ClientRuntime>>,
+    pid: Arc<AtomicU32>,
+    /// Bumped on every (re)start so stale health checks and restarts can detect
+    /// that they no longer apply.
+    generation: u64,
+    started_at: Option<(Instant, i64)>,
+    restarts: u32,
+    consecutive_failures: u32,
+    last_error: Option<String>,
+    log: Arc<ProcessLog>,
+    start_gate: Arc<Mutex<()>>,
+}
+
+impl SupervisedServer {
+    fn new(spec: LaunchSpec, log: Arc<ProcessLog>) -> Self {
+        Self {
+            spec,
+            state: McpProcessState::Starting,
+            client: None,
+            pid: Arc::new(AtomicU32::new(0)),
+            generation: 0,
+            started_at: None,
+            restarts: 0,
+            consecutive_failures: 0,
+            last_error: None,
+            log,
+            start_gate: Arc::new(Mutex::new(())),
+        }
+    }
+
+    fn running_client(&self, spec: &LaunchSpec) -> Option<(Arc<ClientRuntime>, u64)> {
+        if self.state != McpProcessState::Running || &self.spec != spec {
+            return None;
+        }
+        self.client.clone().map(|c| (c, self.generation))
+    }
+
+    fn status(&self, connection_id: &str) -> McpProcessStatus {
+        let pid = self.pid.load(Ordering::Relaxed);
+        McpProcessStatus {
+            connection_id: connection_id.to_string(),
+            state: self.state,
+            pid: (pid != 0).then_some(pid),
+            started_at: self.started_at.map(|(_, ms)| ms),
+            uptime_secs: self.started_at.map(|(t, _)| t.elapsed().as_secs()),
+            restarts: self.restarts,
+            last_error: self.last_error.clone(),
+        }
+    }
+}
+
+/// Forwards server stderr to the process log and picks up the pid marker.
+struct SupervisedClientHandler {
+    connection_id: String,
+    log: Arc<ProcessLog>,
+    pid: Arc<AtomicU32>,
+    registry: Arc<PidRegistry>,
+}
+
+#[async_trait::async_trait]
+impl ClientHandler for SupervisedClientHandler {
+    async fn handle_process_error(
+        &self,
+        error_message: String,
+        _runtime: &dyn McpClient,
+    ) -> Result<(), RpcError> {
+        let pid = error_message
+            .strip_prefix(PID_MARKER)
+            .and_then(|p| p.trim().parse::<u32>().ok());
+        if let Some(pid) = pid {
+            self.pid.store(pid, Ordering::Relaxed);
+            let registry = self.registry.clone();
+            let connection_id = self.connection_id.clone();
+            tokio::task::spawn_blocking(move || registry.record(&connection_id, pid));
+        } else {
+            self.log.append(&error_message);
+        }
+        Ok(())
+    }
+}
+
+pub struct McpProcessSupervisor {
+    servers: Mutex<HashMap<String, SupervisedServer>>,
+    registry: Arc<PidRegistry>,
+    log_dir: Option<PathBuf>,
+}
+
+// Server entries are updated field by field inside short scoped blocks; the lint
+// cannot see that the guard is still needed for the borrowed entry.
+#[allow(clippy::significant_drop_tightening)]
+impl McpProcessSupervisor {
+    /// `data_dir` holds the per-connection logs and the pid registry; without one
+    /// logs are kept in memory only.
+    pub fn new(data_dir: Option<PathBuf>) -> Self {
+        Self {
+            servers: Mutex::new(HashMap::new()),
+            registry: Arc::new(PidRegistry::new(
+                data_dir.as_ref().map(|d| d.join("mcp_processes.json")),
+            )),
+            log_dir: data_dir.map(|d| d.join("mcp_logs")),
+        }
+    }
+
+    fn open_log(&self, connection_id: &str) -> Arc<ProcessLog> {
+        Arc::new(ProcessLog::open(
+            self.log_dir
+                .as_ref()
+                .map(|d| d.join(format!("{connection_id}.log"))),
+        ))
+    }
+
+    /// The running client for a connection, starting the server if needed. Also
+    /// returns the generation to pass to [`Self::report_failure`].
+    pub async fn client_for(
+        self: &Arc<Self>,
+        app: &AppHandle,
+        connection_id: &str,
+        url: &str,
+        env_vars_json: Option<String>,
+        runtime_path: Option<String>,
+    ) -> Result<(Arc<ClientRuntime>, u64), AppError> {
+        let spec = LaunchSpec {
+            url: url.to_string(),
+            env_vars_json,
+            runtime_path,
+        };
+
+        let gate = {
+            let mut servers = self.servers.lock().await;
+            let server = servers.entry(connection_id.to_string()).or_insert_with(|| {
+                SupervisedServer::new(spec.clone(), self.open_log(connection_id))
+            });
+            if let Some(running) = server.running_client(&spec) {
+                return Ok(running);
+            }
+            server.start_gate.clone()
+        };
+
+        let _guard = gate.lock().await;
+        // Another call may have started the server while we waited for the gate.
+        let running = self
+            .servers
+            .lock()
+            .await
+            .get(connection_id)
+            .and_then(|s| s.running_client(&spec));
+        if let Some(running) = running {
+            return Ok(running);
+        }
+
+        self.start(app, connection_id, spec, false).await
+    }
+
+    async fn start(
+        self: &Arc<Self>,
+        app: &AppHandle,
+        connection_id: &str,
+        spec: LaunchSpec,
+        is_restart: bool,
+    ) -> Result<(Arc<ClientRuntime>, u64), AppError> {
+        let (generation, handler, previous) = {
+            let mut servers = self.servers.lock().await;
+            let server = servers.entry(connection_id.to_string()).or_insert_with(|| {
+                SupervisedServer::new(spec.clone(), self.open_log(connection_id))
+            });
+            server.generation += 1;
+            server.state = McpProcessState::Starting;
+            server.spec = spec.clone();
+            server.pid = Arc::new(AtomicU32::new(0));
+            server.started_at = None;
+            if is_restart {
+                server.restarts += 1;
+                server.log.append(&format!(
+                    "[supervisor] restarting (restart #{})",
+                    server.restarts
+                ));
+            } else {
+                server.consecutive_failures = 0;
+                server.log.append("[supervisor] starting");
+            }
+            let handler = SupervisedClientHandler {
+                connection_id: connection_id.to_string(),
+                log: server.log.clone(),
+                pid: server.pid.clone(),
+                registry: self.registry.clone(),
+            };
+            (server.generation, handler, server.client.take())
+        };
+
+        if let Some(previous) = previous {
+            let _ = previous.shut_down().await;
+        }
+
+        let result = MCPClientService::start_supervised_stdio_client(
+            app,
+            spec.url.clone(),
+            spec.env_vars_json.clone(),
+            spec.runtime_path.clone(),
+            handler,
+        )
+        .await;
+
+        let mut servers = self.servers.lock().await;
+        let current = servers
+            .get_mut(connection_id)
+            .filter(|s| s.generation == generation);
+        let Some(server) = current else {
+            drop(servers);
+            if let Ok(client) = result {
+                let _ = client.shut_down().await;
+            }
+            return Err(AppError::Mcp(format!(
+                "MCP server {connection_id} was stopped during startup"
+            )));
+        };
+
+        match result {
+            Ok(client) => {
+                server.client = Some(client.clone());
+                server.state = McpProcessState::Running;
+                server.started_at = Some((Instant::now(), chrono::Utc::now().timestamp_millis()));
+                server.last_error = None;
+                drop(servers);
+                self.spawn_health_check(app.clone(), connection_id.to_string(), generation);
+                Ok((client, generation))
+            }
+            Err(e) => {
+                server
+                    .log
+                    .append(&format!("[supervisor] failed to start: {e}"));
+                if is_restart {
+                    drop(servers);
+                    self.report_failure(app, connection_id, generation, &e.to_string())
+                        .await;
+                } else {
+                    server.state = McpProcessState::Failed;
+                    server.last_error = Some(e.to_string());
+                }
+                Err(e)
+            }
+        }
+    }
+
+    /// Record that the server of `generation` crashed or stopped responding and
+    /// schedule a restart with backoff, giving up after repeated failures.
+    pub async fn report_failure(
+        self: &Arc<Self>,
+        app: &AppHandle,
+        connection_id: &str,
+        generation: u64,
+        error: &str,
+    ) {
+        let (client, pid, delay) = {
+            let mut servers = self.servers.lock().await;
+            let Some(server) = servers.get_mut(connection_id) else {
+                return;
+            };
+            let active = matches!(
+                server.state,
+                McpProcessState::Running | McpProcessState::Starting
+            );
+            if server.generation != generation || !active {
+                return;
+            }
+            if server
+                .started_at
+                .is_some_and(|(t, _)| t.elapsed() >= STABLE_UPTIME)
+            {
+                server.consecutive_failures = 0;
+            }
+            server.last_error = Some(error.to_string());
+            server.started_at = None;
+            server
+                .log
+                .append(&format!("[supervisor] server failed: {error}"));
+
+            let delay = if server.consecutive_failures >= MAX_CONSECUTIVE_RESTARTS {
+                server.state = McpProcessState::Failed;
+                None
+            } else {
+                let delay = restart_delay(server.consecutive_failures);
+                server.consecutive_failures += 1;
+                server.state = McpProcessState::Restarting;
+                Some(delay)
+            };
+            (
+                server.client.take(),
+                server.pid.swap(0, Ordering::Relaxed),
+                delay,
+            )
+        };
+
+        if let Some(client) = client {
+            let _ = client.shut_down().await;
+        }
+        kill_process_group(pid);
+        self.registry.remove(connection_id);
+
+        let Some(delay) = delay else {
+            tracing::error!(
+                "MCP server {connection_id} crashed {MAX_CONSECUTIVE_RESTARTS} times in a row; not restarting"
+            );
+            return;
+        };
+        tracing::warn!(
+            "MCP server {connection_id} failed ({error}); restarting in {}s",
+            delay.as_secs()
+        );
+        tauri::async_runtime::spawn(Self::restart_after(
+            self.clone(),
+            app.clone(),
+            connection_id.to_string(),
+            generation,
+            delay,
+        ));
+    }
+
+    // Boxed to break the `start -> report_failure -> restart_after -> start` cycle.
+    fn restart_after(
+        self: Arc<Self>,
+        app: AppHandle,
+        connection_id: String,
+        generation: u64,
+        delay: Duration,
+    ) -> Pin<Box<dyn Future<Output = ()> + Send>> {
+        Box::pin(async move {
+            tokio::time::sleep(delay).await;
+
+            let gate = {
+                let servers = self.servers.lock().await;
+                match servers.get(&connection_id) {
+                    Some(s) if s.generation == generation => s.start_gate.clone(),
+                    _ => return,
+                }
+            };
+            let _guard = gate.lock().await;
+
+            let spec = {
+                let servers = self.servers.lock().await;
+                match servers.get(&connection_id) {
+                    Some(s)
+                        if s.generation == generation && s.state == McpProcessState::Restarting =>
+                    {
+                        s.spec.clone()
+                    }
+                    _ => return,
+                }
+            };
+
+            if let Err(e) = self.start(&app, &connection_id, spec, true).await {
+                tracing::warn!("Restart of MCP server {connection_id} failed: {e}");
+            }
+        })
+    }
+
+    fn spawn_health_check(
+        self: &Arc<Self>,
+        app: AppHandle,
+        connection_id: String,
+        generation: u64,
+    ) {
+        let supervisor = self.clone();
+        tauri::async_runtime::spawn(async move {
+            loop {
+                tokio::time::sleep(HEALTH_CHECK_INTERVAL).await;
+                let client = {
+                    let servers = supervisor.servers.lock().await;
+                    servers
+                        .get(&connection_id)
+                        .filter(|s| {
+                            s.generation == generation && s.state == McpProcessState::Running
+                        })
+                        .and_then(|s| s.client.clone())
+                };
+                let Some(client) = client else {
+                    break;
+                };
+                if let Err(e) = client.ping(Some(PING_TIMEOUT)).await {
+                    supervisor
+                        .report_failure(
+                            &app,
+                            &connection_id,
+                            generation,
+                            &format!("Health check failed: {e}"),
+                        )
+                        .await;
+                    break;
+                }
+            }
+        });
+    }
+
+    /// Stop the server and restart it immediately, clearing the crash counter.
+    pub async fn restart(
+        self: &Arc<Self>,
+        app: &AppHandle,
+        connection_id: &str,
+    ) -> Result<McpProcessStatus, AppError> {
+        let (gate, spec) = {
+            let servers = self.servers.lock().await;
+            let server = servers.get(connection_id).ok_or_else(|| {
+                AppError::NotFound(format!("No MCP server process for {connection_id}"))
+            })?;
+            (server.start_gate.clone(), server.spec.clone())
+        };
+        let _guard = gate.lock().await;
+        self.start(app, connection_id, spec, false).await?;
+        self.status(connection_id)
+            .await
+            .ok_or_else(|| AppError::NotFound(format!("No MCP server process for {connection_id}")))
+    }
+
+    /// Stop and forget the server for a connection (e.g. when it is deleted or edited).
+    pub async fn stop(&self, connection_id: &str) {
+        let server = self.servers.lock().await.remove(connection_id);
+        if let Some(server) = server {
+            server.log.append("[supervisor] stopped");
+            Self::terminate(server).await;
+        }
+        self.registry.remove(connection_id);
+    }
+
+    /// Stop every supervised server; called when the app exits.
+    pub async fn shutdown_all(&self) {
+        let servers: Vec<SupervisedServer> =
+            self.servers.lock().await.drain().map(|(_, s)| s).collect();
+        for server in servers {
+            Self::terminate(server).await;
+        }
+        self.registry.clear();
+    }
+
+    async fn terminate(server: SupervisedServer) {
+        if let Some(client) = server.client {
+            let _ = client.shut_down().await;
+        }
+        // The transport only kills its direct child; launchers like `npx` leave
+        // the real server behind in the same process group.
+        kill_process_group(server.pid.load(Ordering::Relaxed));
+    }
+
+    pub async fn status(&self, connection_id: &str) -> Option<McpProcessStatus> {
+        self.servers
+            .lock()
+            .await
+            .get(connection_id)
+            .map(|s| s.status(connection_id))
+    }
+
+    pub async fn statuses(&self) -> Vec<McpProcessStatus> {
+        let mut statuses: Vec<McpProcessStatus> = self
+            .servers
+            .lock()
+            .await
+            .iter()
+            .map(|(id, s)| s.status(id))
+            .collect();
+        statuses.sort_by(|a, b| a.connection_id.cmp(&b.connection_id));
+        statuses
+    }
+
+    /// The last `lines` stderr lines, including output from previous app sessions.
+    pub async fn logs(&self, connection_id: &str, lines: usize) -> Vec<String> {
+        let log = self
+            .servers
+            .lock()
+            .await
+            .get(connection_id)
+            .map(|s| s.log.clone());
+        log.unwrap_or_else(|| self.open_log(connection_id))
+            .tail(lines)
+    }
+
+    /// Kill servers left running by a previous session that did not shut down cleanly.
+    pub fn kill_orphans(&self) {
+        for (connection_id, tracked) in self.registry.take_all() {
+            if process_start_time(tracked.pid).as_deref() == Some(tracked.started.as_str()) {
+                tracing::info!(
+                    "Killing orphaned MCP server for connection {connection_id} (pid {})",
+                    tracked.pid
+                );
+                kill_process_group(tracked.pid);
+            }
+        }
+    }
+}
+
+fn restart_delay(consecutive_failures: u32) -> Duration {
+    RESTART_BASE_DELAY
+        .saturating_mul(2u32.saturating_pow(consecutive_failures))
+        .min(RESTART_MAX_DELAY)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct TrackedProcess {
+    pid: u32,
+    /// Process start time as reported by `ps`, guarding against pid reuse.
+    started: String,
+}
+
+/// Pids of running supervised servers, persisted so orphans can be found after a crash.
+struct PidRegistry {
+    path: Option<PathBuf>,
+    entries: std::sync::Mutex<HashMap<String, TrackedProcess>>,
+}
+
+impl PidRegistry {
+    fn new(path: Option<PathBuf>) -> Self {
+        let entries = path
+            .as_ref()
+            .and_then(|p| std::fs::read_to_string(p).ok())
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .unwrap_or_default();
+        Self {
+            path,
+            entries: std::sync::Mutex::new(entries),
+        }
+    }
+
+    fn record(&self, connection_id: &str, pid: u32) {
+        let Some(started) = process_start_time(pid) else {
+            return;
+        };
+        self.update(|entries| {
+            entries.insert(connection_id.to_string(), TrackedProcess { pid, started });
+        });
+    }
+
+    fn remove(&self, connection_id: &str) {
+        self.update(|entries| {
+            entries.remove(connection_id);
+        });
+    }
+
+    fn clear(&self) {
+        self.update(HashMap::clear);
+    }
+
+    fn take_all(&self) -> HashMap<String, TrackedProcess> {
+        let mut taken = HashMap::new();
+        self.update(|entries| taken = std::mem::take(entries));
+        taken
+    }
+
+    fn update(&self, f: impl FnOnce(&mut HashMap<String, TrackedProcess>)) {
+        let Ok(mut entries) = self.entries.lock() else {
+            return;
+        };
+        f(&mut entries);
+        let Some(path) = &self.path else {
+            return;
+        };
+        let result = serde_json::to_string(&*entries)
+            .map_err(std::io::Error::other)
+            .and_then(|json| {
+                if let Some(parent) = path.parent() {
+                    std::fs::create_dir_all(parent)?;
+                }
+                std::fs::write(path, json)
+            });
+        if let Err(e) = result {
+            tracing::warn!("Failed to write MCP pid registry {}: {e}", path.display());
+        }
+    }
+}
+
+#[cfg(unix)]
+fn process_start_time(pid: u32) -> Option<String> {
+    let output = std::process::Command::new("ps")
+        .args(["-o", "lstart=", "-p", &pid.to_string()])
+        .output()
+        .ok()?;
+    let started = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (output.status.success() && !started.is_empty()).then_some(started)
+}
+
+#[cfg(not(unix))]
+fn process_start_time(_pid: u32) -> Option<String> {
+    None
+}
+
+/// Supervised servers lead their own process group, so signalling `-pid` also
+/// reaches any children they spawned.
+#[cfg(unix)]
+fn kill_process_group(pid: u32) {
+    if pid == 0 {
+        return;
+    }
+    let _ = std::process::Command::new("kill")
+        .args(["-TERM", &format!("-{pid}")])
+        .stderr(std::process::Stdio::null())
+        .status();
+}
+
+#[cfg(not(unix))]
+fn kill_process_group(_pid: u32) {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn restart_delay_is_exponential_and_capped() {
+        assert_eq!(restart_delay(0), Duration::from_secs(1));
+        assert_eq!(restart_delay(3), Duration::from_secs(8));
+        assert_eq!(restart_delay(10), RESTART_MAX_DELAY);
+        assert_eq!(restart_delay(u32::MAX), RESTART_MAX_DELAY);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn kill_orphans_kills_recorded_process_groups() {
+        use std::os::unix::process::CommandExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let mut child = std::process::Command::new("sleep")
+            .arg("30")
+            .process_group(0)
+            .spawn()
+            .unwrap();
+
+        let registry = PidRegistry::new(Some(dir.path().join("mcp_processes.json")));
+        registry.record("conn-1", child.id());
+        // A stale entry whose pid has been reused must be left alone.
+        registry.update(|entries| {
+            entries.insert(
+                "conn-2".to_string(),
+                TrackedProcess {
+                    pid: std::process::id(),
+                    started: "never".to_string(),
+                },
+            );
+        });
+
+        let supervisor = McpProcessSupervisor::new(Some(dir.path().to_path_buf()));
+        supervisor.kill_orphans();
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while child.try_wait().unwrap().is_none() {
+            assert!(Instant::now() < deadline, "orphan was not killed");
+            std::thread::sleep(Duration::from_millis(50));
+        }
+        assert!(
+            PidRegistry::new(Some(dir.path().join("mcp_processes.json")))
+                .take_all()
+                .is_empty()
+        );
+    }
+}
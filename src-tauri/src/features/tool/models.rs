@@ -14,3 +14,27 @@ pub struct UnifiedToolInfo {
     pub server_name: String,
     pub description: Option<String>,
 }
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum McpProcessState {
+    Starting,
+    Running,
+    /// Crashed and waiting for the backoff delay before restarting.
+    Restarting,
+    /// Gave up after too many consecutive crashes; the next tool call starts it again.
+    Failed,
+}
+
+/// Runtime status of a supervised stdio MCP server process.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct McpProcessStatus {
+    pub connection_id: String,
+    pub state: McpProcessState,
+    /// Only reported on Unix, where servers are launched through a pid-reporting shim.
+    pub pid: Option<u32>,
+    pub started_at: Option<i64>,
+    pub uptime_secs: Option<u64>,
+    pub restarts: u32,
+    pub last_error: Option<String>,
+}
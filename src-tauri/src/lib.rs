@@ -175,8 +175,12 @@ pub fn run() {
                 log::error!("Initial skill sync failed: {e}");
             }
 
-            // Initialize MCPClientState
-            let mcp_client_state = state::MCPClientState::new();
+            // Initialize MCPClientState; kill stdio servers orphaned by a previous crash
+            let mcp_supervisor = Arc::new(features::tool::mcp::McpProcessSupervisor::new(
+                app.path().app_data_dir().ok(),
+            ));
+            mcp_supervisor.kill_orphans();
+            let mcp_client_state = state::MCPClientState::new(mcp_supervisor);
             app.manage(mcp_client_state);
 
             // Bootstrap sandbox in background (mandatory Python + Node runtimes)
@@ -257,6 +261,11 @@ pub fn run() {
             features::mcp_connection::commands::update_mcp_server_connection,
             features::mcp_connection::commands::delete_mcp_server_connection,
             features::mcp_connection::commands::update_mcp_server_status,
+            features::mcp_connection::commands::preview_mcp_server_import,
+            features::mcp_connection::commands::import_mcp_server_connections,
+            features::mcp_connection::commands::export_mcp_server_connections,
+            features::mcp_connection::commands::authorize_mcp_server_connection,
+            features::mcp_connection::commands::clear_mcp_server_authorization,
             // App Settings commands
             features::app_settings::commands::save_app_setting,
             features::app_settings::commands::get_app_setting,
@@ -270,6 +279,11 @@ pub fn run() {
             features::tool::commands::call_mcp_tool,
             features::tool::commands::disconnect_mcp_client,
             features::tool::commands::get_active_tools_for_workspace,
+            features::tool::commands::get_mcp_server_processes,
+            features::tool::commands::get_mcp_server_process_status,
+            features::tool::commands::get_mcp_server_logs,
+            features::tool::commands::restart_mcp_server_process,
+            features::tool::commands::stop_mcp_server_process,
             // Python commands
             features::runtime::python::commands::get_python_runtimes_status,
             features::runtime::python::commands::install_python_runtime,
@@ -317,8 +331,15 @@ pub fn run() {
             features::browser::commands::browser_reload,
             features::browser::commands::path_to_file_url,
         ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|app, event| {
+            if let tauri::RunEvent::Exit = event {
+                if let Some(mcp_state) = app.try_state::<state::MCPClientState>() {
+                    tauri::async_runtime::block_on(mcp_state.supervisor.shutdown_all());
+                }
+            }
+        });
 }
 
 // Learn more about Tauri commands at https://tauri.app/develop/calling-rust/
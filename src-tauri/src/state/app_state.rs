@@ -10,7 +10,8 @@ use crate::features::llm_connection::{
     LLMConnectionRepository, LLMConnectionService, SqliteLLMConnectionRepository,
 };
 use crate::features::mcp_connection::{
-    MCPConnectionRepository, MCPConnectionService, SqliteMCPConnectionRepository,
+    oauth::OAuthTokenStore, MCPConnectionRepository, MCPConnectionService,
+    SqliteMCPConnectionRepository,
 };
 use crate::features::message::{MessageRepository, MessageService, SqliteMessageRepository};
 
@@ -127,8 +128,11 @@ impl AppState {
         let usage_service = Arc::new(UsageService::new(usage_repo));
         let mcp_connection_repo: Arc<dyn MCPConnectionRepository> =
             Arc::new(SqliteMCPConnectionRepository::new(app.clone()));
-        let mcp_connection_service =
-            Arc::new(MCPConnectionService::new(mcp_connection_repo.clone()));
+        let mcp_oauth_store = Arc::new(OAuthTokenStore::from_app(&app)?);
+        let mcp_connection_service = Arc::new(MCPConnectionService::new(
+            mcp_connection_repo.clone(),
+            mcp_oauth_store,
+        ));
 
         let app_settings_service = Arc::new(AppSettingsService::new(app_settings_repo));
 
@@ -2,6 +2,7 @@ use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::Mutex;
 
+use crate::features::tool::mcp::McpProcessSupervisor;
 use rust_mcp_sdk::mcp_client::ClientRuntime;
 // State to manage persistent MCP client connections
 // Store clients as boxed trait objects to handle different transport types
@@ -26,19 +27,23 @@ pub struct MCPClientState {
 
     // Store active clients
     pub active_clients: Arc<Mutex<HashMap<String, Arc<ClientRuntime>>>>,
+
+    // Long-lived stdio server processes
+    pub supervisor: Arc<McpProcessSupervisor>,
 }
 
 impl MCPClientState {
-    pub fn new() -> Self {
+    pub fn new(supervisor: Arc<McpProcessSupervisor>) -> Self {
         Self {
             connection_info: Arc::new(Mutex::new(HashMap::new())),
             active_clients: Arc::new(Mutex::new(HashMap::new())),
+            supervisor,
         }
     }
 }
 
 impl Default for MCPClientState {
     fn default() -> Self {
-        Self::new()
+        Self::new(Arc::new(McpProcessSupervisor::new(None)))
     }
 }